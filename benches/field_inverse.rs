@@ -0,0 +1,19 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ecdsa::secp256k1::{field_prime, secp256k1_field_inverse};
+use ecdsa::FiniteField;
+use num_bigint::BigUint;
+use num_traits::Num;
+
+// secp256k1 field inverse: fixed addition chain vs generic a^(p-2) via modpow
+fn bench_field_inverse(c: &mut Criterion) {
+    let p = field_prime();
+    let value = BigUint::from_str_radix("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap();
+    let a = FiniteField::new(value, p.clone());
+    let exponent = &p - BigUint::from(2u32);
+
+    c.bench_function("secp256k1 inverse (addition chain)", |b| b.iter(|| secp256k1_field_inverse(black_box(&a)).unwrap()));
+    c.bench_function("secp256k1 inverse (modpow)", |b| b.iter(|| black_box(&a.value).modpow(&exponent, &p)));
+}
+
+criterion_group!(benches, bench_field_inverse);
+criterion_main!(benches);
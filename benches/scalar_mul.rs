@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ecdsa::{EllipticCurve, Point, Secp256k1};
+use num_bigint::BigUint;
+use num_traits::Num;
+
+// the double-add loop through the public add/double, which check every intermediate point
+fn scalar_mul_checked(curve: &EllipticCurve, p: &Point, s: &BigUint) -> Point {
+    let mut res = Point::Identity;
+    for i in (0..s.bits()).rev() {
+        res = curve.double(&res).unwrap();
+        if s.bit(i) {
+            res = curve.add(&res, p).unwrap();
+        }
+    }
+    res
+}
+
+// secp256k1 k*G: double-add with per-step on-curve checks vs scalar_mul's unchecked loop
+fn bench_scalar_mul(c: &mut Criterion) {
+    let curve = Secp256k1::new().elliptic_curve;
+    let s = BigUint::from_str_radix("C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5", 16).unwrap();
+
+    c.bench_function("secp256k1 scalar_mul (checked add/double)", |b| b.iter(|| scalar_mul_checked(&curve, &curve.g, black_box(&s))));
+    c.bench_function("secp256k1 scalar_mul (unchecked loop)", |b| b.iter(|| curve.scalar_mul(&curve.g, black_box(&s)).unwrap()));
+}
+
+criterion_group!(benches, bench_scalar_mul);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use crate::base58;
+use crate::elliptic_curve::{EllipticCurve, Point};
+
+const MAINNET_P2PKH_VERSION: u8 = 0x00;
+const TESTNET_P2PKH_VERSION: u8 = 0x6F;
+
+// Bitcoin P2PKH address: Base58Check(version || RIPEMD-160(SHA-256(SEC1 public key)))
+// compressed and uncompressed encodings of the same key give different addresses
+pub fn public_key_to_address(curve: &EllipticCurve, public_key: &Point, compressed: bool, testnet: bool) -> Result<String, String> {
+    let encoded = curve.serialize_point(public_key, compressed)?;
+    let hash160 = Ripemd160::digest(Sha256::digest(&encoded));
+
+    let mut payload = vec![if testnet { TESTNET_P2PKH_VERSION } else { MAINNET_P2PKH_VERSION }];
+    payload.extend_from_slice(&hash160);
+    Ok(base58::encode_check(&payload))
+}
+
+// Test cases for address generation
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+
+    #[test]
+    fn test_private_key_one_addresses() {
+        // private key 1, so the public key is the generator
+        let curve = Secp256k1::new().elliptic_curve;
+
+        assert_eq!(public_key_to_address(&curve, &curve.g, false, false), Ok("1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm".to_string()));
+        assert_eq!(public_key_to_address(&curve, &curve.g, true, false), Ok("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".to_string()));
+        assert_eq!(public_key_to_address(&curve, &curve.g, false, true), Ok("mtoKs9V381UAhUia3d7Vb9GNak8Qvmcsme".to_string()));
+        assert_eq!(public_key_to_address(&curve, &curve.g, true, true), Ok("mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r".to_string()));
+    }
+
+    #[test]
+    fn test_identity_has_no_address() {
+        let curve = Secp256k1::new().elliptic_curve;
+        assert!(public_key_to_address(&curve, &Point::Identity, true, false).is_err());
+    }
+}
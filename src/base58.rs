@@ -0,0 +1,101 @@
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Base58 encoding, each leading zero byte maps to a leading '1'
+pub fn encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    let mut encoded = "1".repeat(zeros);
+
+    if zeros < bytes.len() {
+        for digit in BigUint::from_bytes_be(&bytes[zeros..]).to_radix_be(58) {
+            encoded.push(ALPHABET[digit as usize] as char);
+        }
+    }
+    encoded
+}
+
+// Base58 decoding, each leading '1' maps back to a zero byte
+pub fn decode(s: &str) -> Result<Vec<u8>, String> {
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    let digits = s.bytes()
+                  .skip(zeros)
+                  .map(|c| ALPHABET.iter()
+                                   .position(|&a| a == c)
+                                   .map(|d| d as u8)
+                                   .ok_or_else(|| format!("Invalid Base58 character '{}'", c as char)))
+                  .collect::<Result<Vec<u8>, String>>()?;
+
+    let mut bytes = vec![0u8; zeros];
+    if !digits.is_empty() {
+        let value = BigUint::from_radix_be(&digits, 58).ok_or("Invalid Base58 string")?;
+        bytes.extend(value.to_bytes_be());
+    }
+    Ok(bytes)
+}
+
+// Base58Check: payload || first 4 bytes of SHA-256(SHA-256(payload))
+pub fn encode_check(payload: &[u8]) -> String {
+    let mut bytes = payload.to_vec();
+    bytes.extend_from_slice(&checksum(payload));
+    encode(&bytes)
+}
+
+// decode Base58Check, validating and stripping the 4-byte checksum
+pub fn decode_check(s: &str) -> Result<Vec<u8>, String> {
+    let bytes = decode(s)?;
+    if bytes.len() < 4 {
+        return Err("Base58Check string is too short".to_string());
+    }
+
+    let (payload, check) = bytes.split_at(bytes.len() - 4);
+    if checksum(payload) != check {
+        return Err("Invalid Base58Check checksum".to_string());
+    }
+    Ok(payload.to_vec())
+}
+
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(payload));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+// Test cases for Base58 and Base58Check
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::hex_bytes;
+
+    #[test]
+    fn test_known_vectors() {
+        // from Bitcoin Core's base58_encode_decode.json
+        let vectors = [
+            ("", ""),
+            ("61", "2g"),
+            ("626262", "a3gV"),
+            ("636363", "aPEr"),
+            ("73696d706c792061206c6f6e6720737472696e67", "2cFupjhnEsSn59qHXstmK2ffpLv2"),
+            ("00eb15231dfceb60925886b67d065299925915aeb172c06647", "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L"),
+            ("516b6fcd0f", "ABnLTmg"),
+            ("00000000000000000000", "1111111111"),
+        ];
+        for (hex, encoded) in vectors {
+            assert_eq!(encode(&hex_bytes(hex)), encoded);
+            assert_eq!(decode(encoded), Ok(hex_bytes(hex)));
+        }
+
+        assert!(decode("0OIl").is_err());
+    }
+
+    #[test]
+    fn test_check_vectors() {
+        // version 0x00 || HASH160 of the compressed generator, the address of private key 1
+        let payload = hex_bytes("00751e76e8199196d454941c45d1b3a323f1433bd6");
+        assert_eq!(encode_check(&payload), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(decode_check("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"), Ok(payload));
+
+        assert_eq!(decode_check("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMJ"), Err("Invalid Base58Check checksum".to_string()));
+        assert_eq!(decode_check("111"), Err("Base58Check string is too short".to_string()));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::elliptic_curve::{EllipticCurve, Point};
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const MAX_LENGTH: usize = 90;
+
+// public key as Bech32(hrp, SEC1 compressed encoding), e.g. for protocols that want a
+// case-insensitive, checksummed string
+pub fn public_key_to_bech32(curve: &EllipticCurve, public_key: &Point, hrp: &str) -> Result<String, String> {
+    encode(hrp, &curve.serialize_point(public_key, true)?)
+}
+
+// parse a Bech32 string produced by public_key_to_bech32, returning the human-readable part too
+pub fn bech32_to_public_key(s: &str, curve: &EllipticCurve) -> Result<(String, Point), String> {
+    let (hrp, bytes) = decode(s)?;
+    Ok((hrp, curve.deserialize_point(&bytes)?))
+}
+
+// BIP-173 Bech32: hrp || '1' || data regrouped into 5-bit words || 6-word checksum
+pub fn encode(hrp: &str, data: &[u8]) -> Result<String, String> {
+    encode_words(hrp, &convert_bits(data, 8, 5, true)?)
+}
+
+// decode a Bech32 string, validating the checksum, into the hrp and the data bytes
+pub fn decode(s: &str) -> Result<(String, Vec<u8>), String> {
+    let (hrp, words) = decode_words(s)?;
+    Ok((hrp, convert_bits(&words, 5, 8, false)?))
+}
+
+fn encode_words(hrp: &str, words: &[u8]) -> Result<String, String> {
+    check_hrp(hrp)?;
+    let hrp = hrp.to_ascii_lowercase();
+    if hrp.len() + 1 + words.len() + 6 > MAX_LENGTH {
+        return Err("Bech32 string would be longer than 90 characters".to_string());
+    }
+
+    let mut values = expand_hrp(&hrp);
+    values.extend_from_slice(words);
+    values.extend_from_slice(&[0; 6]);
+    let checksum = polymod(&values) ^ 1;
+
+    let mut encoded = hrp;
+    encoded.push('1');
+    for word in words.iter().copied().chain((0..6).map(|i| ((checksum >> (5 * (5 - i))) & 31) as u8)) {
+        encoded.push(CHARSET[word as usize] as char);
+    }
+    Ok(encoded)
+}
+
+fn decode_words(s: &str) -> Result<(String, Vec<u8>), String> {
+    if s.len() > MAX_LENGTH {
+        return Err("Bech32 string is longer than 90 characters".to_string());
+    }
+    if s.chars().any(|c| c.is_ascii_lowercase()) && s.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err("Bech32 string mixes upper and lower case".to_string());
+    }
+
+    let s = s.to_ascii_lowercase();
+    let separator = s.rfind('1').ok_or("Bech32 string has no separator")?;
+    let (hrp, data) = (&s[..separator], &s[separator + 1..]);
+    check_hrp(hrp)?;
+    if data.len() < 6 {
+        return Err("Bech32 data part is too short".to_string());
+    }
+
+    let words = data.bytes()
+                    .map(|c| CHARSET.iter()
+                                    .position(|&a| a == c)
+                                    .map(|d| d as u8)
+                                    .ok_or_else(|| format!("Invalid Bech32 character '{}'", c as char)))
+                    .collect::<Result<Vec<u8>, String>>()?;
+
+    let mut values = expand_hrp(hrp);
+    values.extend_from_slice(&words);
+    if polymod(&values) != 1 {
+        return Err("Invalid Bech32 checksum".to_string());
+    }
+
+    Ok((hrp.to_string(), words[..words.len() - 6].to_vec()))
+}
+
+fn check_hrp(hrp: &str) -> Result<(), String> {
+    if hrp.is_empty() || hrp.len() > 83 || hrp.bytes().any(|c| !(33..=126).contains(&c)) {
+        return Err("Invalid Bech32 human-readable part".to_string());
+    }
+    Ok(())
+}
+
+// high bits of each hrp character, a zero, then the low bits
+fn expand_hrp(hrp: &str) -> Vec<u8> {
+    let mut values: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|c| c & 31));
+    values
+}
+
+// BCH checksum over GF(32)
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk = 1u32;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ff_ffff) << 5) ^ *value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+// regroup from-bit words into to-bit words; without padding, leftover bits must be fewer than
+// from and all zero
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, String> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let max = (1u32 << to) - 1;
+    let mut out = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for value in data {
+        acc = (acc << from) | *value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return Err("Invalid Bech32 padding".to_string());
+    }
+    Ok(out)
+}
+
+// Test cases for Bech32 encoding
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_bip173_vectors() {
+        assert_eq!(decode_words("A12UEL5L"), Ok(("a".to_string(), vec![])));
+
+        let words: Vec<u8> = (0..32).collect();
+        assert_eq!(encode_words("abcdef", &words), Ok("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw".to_string()));
+        assert_eq!(decode_words("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"), Ok(("abcdef".to_string(), words)));
+    }
+
+    #[test]
+    fn test_public_key_round_trip() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let public_key = curve.scalar_mul(&curve.g, &BigUint::from(123456789u32)).unwrap();
+
+        let encoded = public_key_to_bech32(&curve, &public_key, "pk").unwrap();
+        assert!(encoded.starts_with("pk1"));
+        assert_eq!(bech32_to_public_key(&encoded, &curve), Ok(("pk".to_string(), public_key.clone())));
+        assert_eq!(bech32_to_public_key(&encoded.to_uppercase(), &curve), Ok(("pk".to_string(), public_key)));
+    }
+
+    #[test]
+    fn test_checksum_failure_rejected() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let encoded = public_key_to_bech32(&curve, &curve.g, "pk").unwrap();
+
+        // swap the last data character for another one of the charset
+        let mut corrupted = encoded[..encoded.len() - 1].to_string();
+        corrupted.push(if encoded.ends_with('q') { 'p' } else { 'q' });
+        assert_eq!(bech32_to_public_key(&corrupted, &curve), Err("Invalid Bech32 checksum".to_string()));
+    }
+}
@@ -0,0 +1,161 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use crate::address::public_key_to_address;
+use crate::base58;
+use crate::ecdsa::{EcdsaSignature, RecoveryId};
+use crate::secp256k1::Secp256k1;
+
+// header = 27 + recovery id, plus 4 when the signing key is compressed
+const HEADER_BASE: u8 = 27;
+const COMPRESSED_FLAG: u8 = 4;
+
+const MESSAGE_MAGIC: &str = "Bitcoin Signed Message:\n";
+const MAINNET_P2PKH_VERSION: u8 = 0x00;
+const TESTNET_P2PKH_VERSION: u8 = 0x6F;
+
+impl EcdsaSignature {
+    // Bitcoin signmessage format: base64(header || r || s), 65 bytes with 32-byte big-endian r and s
+    // returns the signature, its recovery id and whether the signing key was compressed
+    pub fn from_bitcoin_message_sig(b64: &str) -> Result<(EcdsaSignature, RecoveryId, bool), String> {
+        let bytes = STANDARD.decode(b64).map_err(|e| e.to_string())?;
+        if bytes.len() != 65 {
+            return Err("Bitcoin message signature must be 65 bytes".to_string());
+        }
+
+        let header = bytes[0];
+        if !(HEADER_BASE..HEADER_BASE + 2 * COMPRESSED_FLAG).contains(&header) {
+            return Err("Invalid Bitcoin message signature header".to_string());
+        }
+        let compressed = header >= HEADER_BASE + COMPRESSED_FLAG;
+        let recovery_id = RecoveryId::new((header - HEADER_BASE) % COMPRESSED_FLAG)?;
+
+        let signature = EcdsaSignature {
+            r: BigUint::from_bytes_be(&bytes[1..33]),
+            s: BigUint::from_bytes_be(&bytes[33..65]),
+        };
+        Ok((signature, recovery_id, compressed))
+    }
+
+    // inverse of from_bitcoin_message_sig
+    pub fn to_bitcoin_message_sig(&self, recovery_id: RecoveryId, compressed: bool) -> Result<String, String> {
+        let mut bytes = vec![HEADER_BASE + recovery_id.to_u8() + if compressed { COMPRESSED_FLAG } else { 0 }];
+        for value in [&self.r, &self.s] {
+            let encoded = value.to_bytes_be();
+            if encoded.len() > 32 {
+                return Err("r and s must fit in 32 bytes".to_string());
+            }
+            bytes.resize(bytes.len() + 32 - encoded.len(), 0);
+            bytes.extend(encoded);
+        }
+        Ok(STANDARD.encode(bytes))
+    }
+}
+
+// SHA-256(SHA-256(compact size || magic || compact size || message)), the digest signmessage signs
+pub fn bitcoin_message_hash(message: &str) -> [u8; 32] {
+    let mut data = Vec::new();
+    for part in [MESSAGE_MAGIC.as_bytes(), message.as_bytes()] {
+        write_compact_size(&mut data, part.len() as u64);
+        data.extend_from_slice(part);
+    }
+    Sha256::digest(Sha256::digest(&data)).into()
+}
+
+// Check a signmessage signature against a P2PKH address: recover the public key from the
+// signature, derive its address (compressed or not as the header says) and compare
+pub fn verify_bitcoin_message(address: &str, message: &str, signature_b64: &str) -> Result<bool, String> {
+    let testnet = match base58::decode_check(address)?.first() {
+        Some(&MAINNET_P2PKH_VERSION) => false,
+        Some(&TESTNET_P2PKH_VERSION) => true,
+        _ => return Err("Only P2PKH addresses are supported".to_string()),
+    };
+
+    let (signature, recovery_id, compressed) = EcdsaSignature::from_bitcoin_message_sig(signature_b64)?;
+    let curve = Secp256k1::new().elliptic_curve;
+    let public_key = match EcdsaSignature::recover_public_key(&curve, &bitcoin_message_hash(message), &signature, recovery_id) {
+        Ok(public_key) => public_key,
+        Err(_) => return Ok(false),
+    };
+
+    Ok(public_key_to_address(&curve, &public_key, compressed, testnet)? == address)
+}
+
+// Bitcoin's variable-length integer
+fn write_compact_size(out: &mut Vec<u8>, n: u64) {
+    match n {
+        0..=0xFC => out.push(n as u8),
+        0xFD..=0xFFFF => {
+            out.push(0xFD);
+            out.extend_from_slice(&(n as u16).to_le_bytes());
+        }
+        0x1_0000..=0xFFFF_FFFF => {
+            out.push(0xFE);
+            out.extend_from_slice(&(n as u32).to_le_bytes());
+        }
+        _ => {
+            out.push(0xFF);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+    }
+}
+
+// Test cases for Bitcoin message signatures
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::Num;
+
+    // Bitcoin Core's rpc_signmessage test: "This is just a test message" signed with
+    // cUeKHd5orzT3mz8P9pxyREHfsWtVfgsfDjiZZBcjUBAaGk1BTj7N
+    const KNOWN_SIGNATURE: &str = "INbVnW4e6PeRmsv2Qgu8NuopvrVjkcxob+sX8OcZG0SALhWybUjzMLPdAsXI46YZGb0KQTRii+wWIQzRpG/U+S0=";
+
+    #[test]
+    fn test_decode_known_signature() {
+        let (signature, recovery_id, compressed) = EcdsaSignature::from_bitcoin_message_sig(KNOWN_SIGNATURE).unwrap();
+
+        // header 32 = 27 + 1 + 4
+        assert_eq!(recovery_id, RecoveryId::new(1).unwrap());
+        assert!(compressed);
+        assert_eq!(signature.r, BigUint::from_str_radix("D6D59D6E1EE8F7919ACBF6420BBC36EA29BEB56391CC686FEB17F0E7191B4480", 16).unwrap());
+        assert_eq!(signature.s, BigUint::from_str_radix("2E15B26D48F330B3DD02C5C8E3A61919BD0A4134628BEC16210CD1A46FD4F92D", 16).unwrap());
+
+        assert_eq!(signature.to_bitcoin_message_sig(recovery_id, compressed), Ok(KNOWN_SIGNATURE.to_string()));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // small r needs left padding
+        let signature = EcdsaSignature { r: BigUint::from(5u32), s: BigUint::from(0xABCDu32) };
+        for id in 0..4 {
+            for compressed in [false, true] {
+                let encoded = signature.to_bitcoin_message_sig(RecoveryId::new(id).unwrap(), compressed).unwrap();
+                let (decoded, recovery_id, decoded_compressed) = EcdsaSignature::from_bitcoin_message_sig(&encoded).unwrap();
+                assert_eq!((decoded.r, decoded.s), (signature.r.clone(), signature.s.clone()));
+                assert_eq!((recovery_id.to_u8(), decoded_compressed), (id, compressed));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        assert!(EcdsaSignature::from_bitcoin_message_sig("not base64!").is_err());
+        assert!(EcdsaSignature::from_bitcoin_message_sig(&STANDARD.encode([31u8; 64])).is_err());
+
+        let mut bytes = STANDARD.decode(KNOWN_SIGNATURE).unwrap();
+        bytes[0] = 35;
+        assert!(EcdsaSignature::from_bitcoin_message_sig(&STANDARD.encode(&bytes)).is_err());
+        assert!(RecoveryId::new(4).is_err());
+    }
+
+    #[test]
+    fn test_verify_known_message() {
+        // the address of cUeKHd5orzT3mz8P9pxyREHfsWtVfgsfDjiZZBcjUBAaGk1BTj7N
+        let address = "mpLQjfK79b7CCV4VMJWEWAj5Mpx8Up5zxB";
+        assert_eq!(verify_bitcoin_message(address, "This is just a test message", KNOWN_SIGNATURE), Ok(true));
+        assert_eq!(verify_bitcoin_message(address, "This is just a test message!", KNOWN_SIGNATURE), Ok(false));
+        assert_eq!(verify_bitcoin_message("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH", "This is just a test message", KNOWN_SIGNATURE), Ok(false));
+        assert!(verify_bitcoin_message("mpLQjfK79b7CCV4VMJWEWAj5Mpx8Up5zxC", "This is just a test message", KNOWN_SIGNATURE).is_err());
+    }
+}
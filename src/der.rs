@@ -0,0 +1,177 @@
+use num_bigint::BigUint;
+use crate::ecdsa::EcdsaSignature;
+
+const SEQUENCE_TAG: u8 = 0x30;
+const INTEGER_TAG: u8 = 0x02;
+
+impl EcdsaSignature {
+    // DER: SEQUENCE { INTEGER r, INTEGER s }, minimal lengths, integers zero-padded only when
+    // their top bit is set
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut body = encode_integer(&self.r);
+        body.extend(encode_integer(&self.s));
+
+        let mut der = vec![SEQUENCE_TAG];
+        der.extend(encode_length(body.len()));
+        der.extend(body);
+        der
+    }
+
+    // parse DER, also accepting the non-minimal encodings some signers emit (long-form lengths
+    // below 128, length bytes with leading zeros, integers with superfluous 0x00 padding)
+    // the bool is true when the input was strictly canonical DER
+    pub fn from_der_lenient(bytes: &[u8]) -> Result<(EcdsaSignature, bool), String> {
+        let mut reader = DerReader { bytes, pos: 0, canonical: true };
+
+        reader.expect_tag(SEQUENCE_TAG)?;
+        let length = reader.read_length()?;
+        if length != bytes.len() - reader.pos {
+            return Err("DER sequence length does not match the input".to_string());
+        }
+
+        let r = reader.read_integer()?;
+        let s = reader.read_integer()?;
+        if reader.pos != bytes.len() {
+            return Err("Trailing bytes after the DER integers".to_string());
+        }
+
+        Ok((EcdsaSignature { r, s }, reader.canonical))
+    }
+}
+
+struct DerReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    canonical: bool,
+}
+
+impl<'a> DerReader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(count).filter(|end| *end <= self.bytes.len()).ok_or("Truncated DER signature")?;
+        let taken = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(taken)
+    }
+
+    fn expect_tag(&mut self, tag: u8) -> Result<(), String> {
+        if self.take(1)?[0] != tag {
+            return Err(format!("Expected DER tag 0x{:02x}", tag));
+        }
+        Ok(())
+    }
+
+    // short form below 128, otherwise 0x80 | count followed by count big-endian length bytes
+    fn read_length(&mut self) -> Result<usize, String> {
+        let first = self.take(1)?[0];
+        if first < 0x80 {
+            return Ok(first as usize);
+        }
+
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > std::mem::size_of::<usize>() {
+            return Err("Unsupported DER length encoding".to_string());
+        }
+        let length_bytes = self.take(count)?;
+        let length = length_bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+
+        if length < 0x80 || length_bytes[0] == 0 {
+            self.canonical = false;
+        }
+        Ok(length)
+    }
+
+    // a positive INTEGER; negative values are rejected even in lenient mode
+    fn read_integer(&mut self) -> Result<BigUint, String> {
+        self.expect_tag(INTEGER_TAG)?;
+        let length = self.read_length()?;
+        let content = self.take(length)?;
+
+        match content {
+            [] => return Err("Empty DER integer".to_string()),
+            [first, ..] if first & 0x80 != 0 => return Err("Negative DER integer".to_string()),
+            [0, second, ..] if second & 0x80 == 0 => self.canonical = false,
+            _ => {}
+        }
+        Ok(BigUint::from_bytes_be(content))
+    }
+}
+
+fn encode_integer(value: &BigUint) -> Vec<u8> {
+    let mut content = value.to_bytes_be();
+    if content[0] & 0x80 != 0 {
+        content.insert(0, 0);
+    }
+
+    let mut encoded = vec![INTEGER_TAG];
+    encoded.extend(encode_length(content.len()));
+    encoded.extend(content);
+    encoded
+}
+
+fn encode_length(length: usize) -> Vec<u8> {
+    if length < 0x80 {
+        return vec![length as u8];
+    }
+    let bytes: Vec<u8> = length.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+    let mut encoded = vec![0x80 | bytes.len() as u8];
+    encoded.extend(bytes);
+    encoded
+}
+
+// Test cases for DER encoding
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::Num;
+
+    fn signature() -> EcdsaSignature {
+        // r has its top bit set, so it needs a 0x00 pad
+        EcdsaSignature {
+            r: BigUint::from_str_radix("8F4B4F8E1EAE2F1AF9DA1E5CD3B09E1BD5D3F0A1C7EC4A3F0CE7F8B2D0E3A1C5", 16).unwrap(),
+            s: BigUint::from_str_radix("3A2B1C0D9E8F7A6B5C4D3E2F1A0B9C8D7E6F5A4B3C2D1E0F9A8B7C6D5E4F3A2B", 16).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_canonical_der() {
+        let der = signature().to_der();
+        assert_eq!(&der[..6], &[0x30, 0x45, 0x02, 0x21, 0x00, 0x8F]);
+
+        let (parsed, canonical) = EcdsaSignature::from_der_lenient(&der).unwrap();
+        assert!(canonical);
+        assert_eq!((parsed.r, parsed.s), (signature().r, signature().s));
+    }
+
+    #[test]
+    fn test_non_minimal_der() {
+        let s = signature().s.to_bytes_be();
+
+        // s padded with a superfluous zero byte and the sequence length in long form
+        let mut body = vec![0x02, 0x21, 0x00];
+        body.extend(signature().r.to_bytes_be());
+        body.extend([0x02, 0x21, 0x00]);
+        body.extend(&s);
+        let mut der = vec![0x30, 0x81, body.len() as u8];
+        der.extend(&body);
+
+        let (parsed, canonical) = EcdsaSignature::from_der_lenient(&der).unwrap();
+        assert!(!canonical);
+        assert_eq!((parsed.r, parsed.s), (signature().r, signature().s));
+    }
+
+    #[test]
+    fn test_malformed_der_rejected() {
+        let der = signature().to_der();
+        assert!(EcdsaSignature::from_der_lenient(&der[..der.len() - 1]).is_err());
+
+        let mut trailing = der.clone();
+        trailing.push(0);
+        assert!(EcdsaSignature::from_der_lenient(&trailing).is_err());
+
+        // r without its 0x00 pad reads as negative
+        let mut negative = vec![0x30, 0x44, 0x02, 0x20];
+        negative.extend(signature().r.to_bytes_be());
+        negative.extend(&der[der.len() - 34..]);
+        assert_eq!(EcdsaSignature::from_der_lenient(&negative).err(), Some("Negative DER integer".to_string()));
+    }
+}
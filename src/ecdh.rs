@@ -0,0 +1,88 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+use crate::elliptic_curve::{EllipticCurve, Point};
+
+// ECDH shared secret: the x-coordinate of my_priv * their_pub, fixed-width big-endian (SEC1 Z)
+// the raw x-coordinate is not uniformly random, so run it through a KDF before using it as a key
+pub fn derive_shared(curve: &EllipticCurve, my_priv: &BigUint, their_pub: &Point) -> Result<Vec<u8>, String> {
+    check_inputs(curve, my_priv, their_pub)?;
+    Ok(curve.scalar_mul_x_only(their_pub, my_priv)?.to_bytes_be())
+}
+
+// Group key agreement by cascading ECDH around a ring of m parties (Ingemarsson-Tang-Wong):
+// each party sends its public key to the next party in the ring, then for m - 2 more rounds
+// multiplies whatever it received by its private key (cascade_step) and passes the result on.
+// The point a party receives in the last round carries every other party's key, so
+// derive_shared_multi on it gives the x-coordinate of (x_1 * ... * x_m) * G for every party.
+//
+// Pairwise secrets alone cannot do this: for three parties, A only knows ab*G and ac*G while
+// B only knows ab*G and bc*G, so no sum or hash of them agrees. The cascade costs m - 1
+// sequential rounds, is unauthenticated (a man-in-the-middle can substitute partial keys just
+// as in two-party ECDH) and has to be rerun whenever the membership changes.
+pub fn cascade_step(curve: &EllipticCurve, my_priv: &BigUint, received: &Point) -> Result<Point, String> {
+    multiply_checked(curve, my_priv, received)
+}
+
+// the group secret from the partial key received in the last round of the cascade
+pub fn derive_shared_multi(curve: &EllipticCurve, my_priv: &BigUint, their_partial: &Point) -> Result<Vec<u8>, String> {
+    derive_shared(curve, my_priv, their_partial)
+}
+
+// my_priv * point, after checking the key is in [1, n-1] and the point is a valid public key
+fn multiply_checked(curve: &EllipticCurve, my_priv: &BigUint, point: &Point) -> Result<Point, String> {
+    check_inputs(curve, my_priv, point)?;
+    Ok(curve.scalar_mul(point, my_priv)?)
+}
+
+fn check_inputs(curve: &EllipticCurve, my_priv: &BigUint, point: &Point) -> Result<(), String> {
+    if my_priv.is_zero() || my_priv >= &curve.n {
+        return Err("Private key must be in the range [1, n-1]".to_string());
+    }
+    Ok(curve.validate_public_key(point)?)
+}
+
+// Test cases for ECDH
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecdsa::EcdsaKeyPair;
+    use crate::secp256k1::Secp256k1;
+
+    #[test]
+    fn test_derive_shared_two_parties() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let alice = EcdsaKeyPair::generate(&curve);
+        let bob = EcdsaKeyPair::generate(&curve);
+
+        let alice_secret = derive_shared(&curve, &alice.private_key, &bob.public_key).unwrap();
+        let bob_secret = derive_shared(&curve, &bob.private_key, &alice.public_key).unwrap();
+        assert_eq!(alice_secret, bob_secret);
+        assert_eq!(alice_secret.len(), 32);
+
+        assert!(derive_shared(&curve, &alice.private_key, &Point::Identity).is_err());
+        assert!(derive_shared(&curve, &BigUint::zero(), &bob.public_key).is_err());
+    }
+
+    #[test]
+    fn test_derive_shared_multi_three_parties() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let parties: Vec<EcdsaKeyPair> = (0..3).map(|_| EcdsaKeyPair::generate(&curve)).collect();
+
+        // ring 0 -> 1 -> 2 -> 0: round one passes public keys, round two one cascade step
+        let round_one: Vec<Point> = (0..3).map(|i| parties[(i + 2) % 3].public_key.clone()).collect();
+        let round_two: Vec<Point> = (0..3)
+            .map(|i| cascade_step(&curve, &parties[(i + 2) % 3].private_key, &round_one[(i + 2) % 3]).unwrap())
+            .collect();
+
+        let secrets: Vec<Vec<u8>> = (0..3)
+            .map(|i| derive_shared_multi(&curve, &parties[i].private_key, &round_two[i]).unwrap())
+            .collect();
+        assert_eq!(secrets[0], secrets[1]);
+        assert_eq!(secrets[1], secrets[2]);
+
+        // all three keys times G
+        let product = parties.iter().fold(BigUint::from(1u32), |acc, party| acc * &party.private_key) % &curve.n;
+        let group_point = curve.scalar_mul(&curve.g, &product).unwrap();
+        assert_eq!(secrets[0], group_point.x().unwrap().to_bytes_be());
+    }
+}
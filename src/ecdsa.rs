@@ -1,11 +1,19 @@
-pub use crate::elliptic_curve::{EllipticCurve, Point};
+pub use crate::elliptic_curve::{EllipticCurve, Point, PrecomputedBase};
 pub use crate::finite_field::FiniteField;
+use crate::scalar::Scalar;
 use num_bigint::{BigUint, RandBigInt};
 use sha2::{Digest, Sha256};
 use rand::rngs::OsRng;
-use num_traits::{Num, Zero};
+use rand::{CryptoRng, RngCore};
+use num_traits::Zero;
+use std::collections::HashSet;
+use std::io::Write;
 
 // ECDSA Key Pair
+// the public key pair and signature types keep r, s and the private key as plain BigUint, since
+// a Scalar would reduce them mod n on construction: values decoded from DER, compact or Bitcoin
+// signatures (which do not know n) and caller-supplied keys must be range-checked and rejected
+// instead; the arithmetic on them goes through Scalar inside signing, verification and recovery
 pub struct EcdsaKeyPair {
     pub private_key: BigUint,
     pub public_key: Point,
@@ -18,99 +26,460 @@ pub struct EcdsaSignature {
     pub s: BigUint,
 }
 
+// Incremental signer for messages too large to hold in memory: chunks passed to update are
+// hashed as they arrive and finalize signs the SHA-256 digest, same as sign on the whole message
+pub struct EcdsaSigner<'a> {
+    curve: &'a EllipticCurve,
+    private_key: &'a BigUint,
+    hasher: Sha256,
+}
+
+// Which of the up to four points with x-coordinate r (or r + n) was R = k*G:
+// bit 0 is the parity of R.y, bit 1 is set when R.x overflowed n
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecoveryId(u8);
+
+impl RecoveryId {
+    pub fn new(id: u8) -> Result<Self, String> {
+        if id > 3 {
+            return Err("Recovery id must be between 0 and 3".to_string());
+        }
+        Ok(RecoveryId(id))
+    }
+
+    pub fn to_u8(self) -> u8 {
+        self.0
+    }
+}
+
+// Public keys that already passed validation (on the curve, not the identity and n*Q = Identity),
+// keyed by their SEC1 compressed encoding; a cache should only be used with a single curve
+#[derive(Default)]
+pub struct ValidatedKeyCache {
+    validated: HashSet<Vec<u8>>,
+}
+
+impl ValidatedKeyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // validate the key unless it was validated before
+    // serialize_point already rejects the identity and off-curve points, leaving the n*Q check
+    pub fn validate(&mut self, curve: &EllipticCurve, public_key: &Point) -> Result<(), String> {
+        let key = curve.serialize_point(public_key, true)?;
+        if self.validated.contains(&key) {
+            return Ok(());
+        }
+
+        if curve.scalar_mul(public_key, &curve.n)? != Point::Identity {
+            return Err("Public key is not in the subgroup of order n".to_string());
+        }
+        self.validated.insert(key);
+        Ok(())
+    }
+}
+
 impl EcdsaKeyPair {
     // Efficient key generation, minimizing cloning
     pub fn generate(curve: &EllipticCurve) -> Self {
-        let mut rng = OsRng;
-        let private_key = rng.gen_biguint_below(&curve.p);
-        let public_key = curve.scalar_mul(&curve.g, private_key.clone())
+        EcdsaKeyPair::generate_with_rng(curve, &mut OsRng)
+    }
+
+    // Key generation from any cryptographically secure RNG (a seeded one for reproducible tests,
+    // a hardware one, ...); the private key is drawn uniformly from [1, n)
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(curve: &EllipticCurve, rng: &mut R) -> Self {
+        let private_key = generate_nonzero_random(rng, &curve.n);
+        let public_key = curve.mul_generator(&private_key)
                              .expect("Scalar multiplication failed");
 
         EcdsaKeyPair { private_key, public_key }
     }
+
+    // Deterministic key pair for reproducible tests and examples, never for real keys:
+    // d = SHA-256(seed) mod (n - 1) + 1, which always lands in [1, n-1]
+    pub fn generate_from_seed(curve: &EllipticCurve, seed: &[u8]) -> Result<Self, String> {
+        let hash = BigUint::from_bytes_be(&Sha256::digest(seed));
+        let private_key = hash % (&curve.n - 1u32) + 1u32;
+        let public_key = curve.mul_generator(&private_key)?;
+
+        Ok(EcdsaKeyPair { private_key, public_key })
+    }
 }
 
 impl EcdsaSignature {
     // Refactored signature function to improve clarity and error handling
+    // scalars (k, r, s and the private key) live in Z_n, not in the base field Fp
     pub fn sign(curve: &EllipticCurve, message: &[u8], private_key: &BigUint) -> Result<Self, String> {
-        let hash = hash_message(message);
-        let mut rng = OsRng;
-        let k = generate_nonzero_random(&mut rng, &curve.p);
+        EcdsaSignature::sign_prehashed(curve, &hash_message(message), private_key)
+    }
+
+    // Sign an already computed digest (from a streaming hash, SHA-512, keccak256, ...)
+    // the digest is truncated to the leftmost bitlen(n) bits per FIPS 186-4
+    pub fn sign_prehashed(curve: &EllipticCurve, digest: &[u8], private_key: &BigUint) -> Result<Self, String> {
+        let private_key = EcdsaSignature::private_scalar(curve, private_key)?;
+        Ok(EcdsaSignature::sign_digest(curve, digest, &private_key, None)?.0)
+    }
+
+    // sign, also returning the recovery id of the nonce point R (parity of R.y, and whether R.x
+    // was reduced mod n) so that recover_public_key can rebuild the signer's key
+    pub fn sign_recoverable(curve: &EllipticCurve, message: &[u8], private_key: &BigUint) -> Result<(Self, RecoveryId), String> {
+        let (signature, r_point) = EcdsaSignature::sign_full(curve, message, private_key)?;
+        let (x, y) = r_point.into_coords().ok_or("Invalid r_point generated")?;
+        Ok((signature, RecoveryId(u8::from(y.bit(0)) | if x >= curve.n { 2 } else { 0 })))
+    }
+
+    // sign, also returning the nonce point R = k*G itself (r is R.x mod n), for protocols
+    // that need R without recomputing k*G
+    pub fn sign_full(curve: &EllipticCurve, message: &[u8], private_key: &BigUint) -> Result<(Self, Point), String> {
+        let private_key = EcdsaSignature::private_scalar(curve, private_key)?;
+        EcdsaSignature::sign_digest(curve, &hash_message(message), &private_key, None)
+    }
+
+    // Sign with a caller-chosen nonce, for reproducing published test vectors
+    // test/advanced use only: a k that is reused or guessable reveals the private key
+    pub fn sign_with_nonce(curve: &EllipticCurve, message: &[u8], private_key: &BigUint, k: &BigUint) -> Result<Self, String> {
+        if k.is_zero() || k >= &curve.n {
+            return Err("Nonce must be in the range [1, n-1]".to_string());
+        }
+
+        let private_key = EcdsaSignature::private_scalar(curve, private_key)?;
+        EcdsaSignature::sign_prehashed_with_nonce(curve, &hash_message(message), &private_key, k, None)?
+            .map(|(signature, _)| signature)
+            .ok_or_else(|| "Nonce yields r = 0 or s = 0".to_string())
+    }
+
+    // Sign many independent messages with one key, each with its own fresh nonce
+    // the key is range-checked and converted to a Scalar once, k*G goes through the shared generator table
+    pub fn sign_many(curve: &EllipticCurve, messages: &[&[u8]], private_key: &BigUint) -> Result<Vec<Self>, String> {
+        let private_key = EcdsaSignature::private_scalar(curve, private_key)?;
+
+        messages.iter()
+                .map(|message| Ok(EcdsaSignature::sign_digest(curve, &hash_message(message), &private_key, None)?.0))
+                .collect()
+    }
 
-        let r_point = curve.scalar_mul(&curve.g, k.clone())
+    // Same as sign, but writes every intermediate value of the computation to out (hash, truncated
+    // hash, k, R, r, s and the low-S form of s) in the layout of OpenSSL's -text output, so a trace
+    // can be diffed against reference tools; the returned s is not low-S normalized
+    pub fn sign_verbose(curve: &EllipticCurve, message: &[u8], private_key: &BigUint, out: &mut dyn Write) -> Result<Self, String> {
+        let private_key = EcdsaSignature::private_scalar(curve, private_key)?;
+        Ok(EcdsaSignature::sign_digest(curve, &hash_message(message), &private_key, Some(out))?.0)
+    }
+
+    // the per-key work shared by every signing entry point: the range check and the Scalar conversion
+    fn private_scalar(curve: &EllipticCurve, private_key: &BigUint) -> Result<Scalar, String> {
+        if private_key.is_zero() || private_key >= &curve.n {
+            return Err("Private key must be in the range [1, n-1]".to_string());
+        }
+
+        Ok(Scalar::new(private_key.clone(), curve.n.clone()))
+    }
+
+    // draws fresh nonces until one yields r != 0 and s != 0
+    fn sign_digest(curve: &EllipticCurve, digest: &[u8], private_key: &Scalar, mut trace: Option<&mut dyn Write>) -> Result<(Self, Point), String> {
+        loop {
+            let k = generate_nonzero_random(&mut OsRng, &curve.n);
+            if let Some(signed) = EcdsaSignature::sign_prehashed_with_nonce(curve, digest, private_key, &k, trace.as_mut().map(|out| &mut **out as &mut dyn Write))? {
+                return Ok(signed);
+            }
+        }
+    }
+
+    // None when this k yields r = 0 or s = 0, in which case the caller retries with a fresh nonce
+    // the nonce point R comes along for callers that need it
+    fn sign_prehashed_with_nonce(curve: &EllipticCurve, digest: &[u8], private_key: &Scalar, k: &BigUint, mut trace: Option<&mut dyn Write>) -> Result<Option<(Self, Point)>, String> {
+        let r_point = curve.mul_generator(k)
                           .map_err(|e| e.to_string())?;
 
-        if let Point::Coor(x, _) = r_point {
-            let r_field = FiniteField::new(x.get_value().clone(), curve.p.clone());
-            let private_key_field = FiniteField::new(private_key.clone(), curve.p.clone());
-            let hash_field = FiniteField::new(hash, curve.p.clone());
-            
-            let s_field = calculate_s_field(&hash_field, &r_field, &private_key_field, &k, &curve.p)?;
-            println!("r: {:?}, s: {:?}", r_field.get_value(), s_field.get_value());
-            Ok(EcdsaSignature { r: r_field.get_value().clone(), s: s_field.get_value().clone() })
-        } else {
-            Err("Invalid r_point generated".to_string())
+        let (x, y) = r_point.coords().ok_or("Invalid r_point generated")?;
+
+        let r = Scalar::new(x.clone(), curve.n.clone());
+        let hash = Scalar::from_hash(digest, &curve.n);
+        let k = Scalar::new(k.clone(), curve.n.clone());
+
+        let s = calculate_s(&hash, &r, private_key, &k)?;
+        if r.is_zero() || s.is_zero() {
+            return Ok(None);
         }
+
+        if let Some(out) = trace.as_mut() {
+            let low_s = if s.get_value() > &(&curve.n >> 1) { s.neg() } else { s.clone() };
+            write_openssl_hex(&mut **out, "hash", digest)?;
+            let lines = [
+                ("truncated hash", hash.get_value()),
+                ("k", k.get_value()),
+                ("R.x", &x),
+                ("R.y", &y),
+                ("r", r.get_value()),
+                ("s", s.get_value()),
+                // informational only, the signature keeps s as computed
+                ("low-S s (not applied)", low_s.get_value()),
+            ];
+            for (label, value) in lines {
+                write_openssl_hex(&mut **out, label, &openssl_bn_bytes(value))?;
+            }
+        }
+
+        Ok(Some((EcdsaSignature { r: r.into(), s: s.into() }, r_point)))
     }
 
     // Verification function with improved error handling
     pub fn verify(curve: &EllipticCurve, message: &[u8], public_key: &Point, signature: &EcdsaSignature) -> Result<bool, String> {
-        let hash = hash_message(message);
-        let hash_field = FiniteField::new(hash, curve.p.clone());
+        curve.validate_public_key(public_key)?;
+        EcdsaSignature::verify_validated_key(curve, message, public_key, signature)
+    }
+
+    // verify for a public key that already passed validation
+    fn verify_validated_key(curve: &EllipticCurve, message: &[u8], public_key: &Point, signature: &EcdsaSignature) -> Result<bool, String> {
+        let p = EcdsaSignature::verify_recovering_point(curve, message, public_key, signature)?;
+        let x = p.x().ok_or("Invalid point generated in verification")?;
 
-        let signature_s_field = FiniteField::new(signature.s.clone(), curve.p.clone());
-        let one_field = FiniteField::new(BigUint::from(1u32), curve.p.clone());
+        // r was R.x mod n when signing, so compare modulo the group order, not p
+        Ok(x.get_value() % &curve.n == signature.r)
+    }
 
-        let w = one_field.div(&signature_s_field)
-                         .map_err(|e| e.to_string())?;
+    // fixed 64-byte r || s encoding (libsecp256k1 "compact"), each half 32-byte big-endian
+    // r and s must be reduced mod n, and n must fit in 32 bytes (so not P-384)
+    pub fn to_compact_bytes(&self, n: &BigUint) -> Result<[u8; 64], String> {
+        if n.bits() > 256 {
+            return Err("Compact encoding needs a group order of at most 32 bytes".to_string());
+        }
+        if self.r >= *n || self.s >= *n {
+            return Err("Signature r and s must be less than n".to_string());
+        }
 
-        let u1 = hash_field.mul(&w)?;
-        let u2 = FiniteField::new(signature.r.clone(), curve.p.clone()).mul(&w)?;
+        let mut bytes = [0u8; 64];
+        let r = self.r.to_bytes_be();
+        let s = self.s.to_bytes_be();
+        bytes[32 - r.len()..32].copy_from_slice(&r);
+        bytes[64 - s.len()..].copy_from_slice(&s);
+        Ok(bytes)
+    }
 
-        let u1_point = curve.scalar_mul(&curve.g, u1.get_value().clone())?;
-        let u2_point = curve.scalar_mul(public_key, u2.get_value().clone())?;
+    // parse the 64-byte r || s encoding, rejecting halves outside [1, n-1]
+    pub fn from_compact_bytes(bytes: &[u8; 64], n: &BigUint) -> Result<Self, String> {
+        let r = BigUint::from_bytes_be(&bytes[..32]);
+        let s = BigUint::from_bytes_be(&bytes[32..]);
+        if r.is_zero() || &r >= n || s.is_zero() || &s >= n {
+            return Err("Signature r and s must be in the range [1, n-1]".to_string());
+        }
+        Ok(EcdsaSignature { r, s })
+    }
 
-        let p = curve.add(&u1_point, &u2_point)
-                    .map_err(|e| e.to_string())?;
-        
-        match p {
-            Point::Coor(x, _) => Ok(x == FiniteField::new(signature.r.clone(), curve.p.clone())),
-            _ => Err("Invalid point generated in verification".to_string()),
+    // verify for callers holding r and s as separate integers
+    pub fn verify_rs(curve: &EllipticCurve, message: &[u8], public_key: &Point, r: &BigUint, s: &BigUint) -> Result<bool, String> {
+        let signature = EcdsaSignature { r: r.clone(), s: s.clone() };
+        EcdsaSignature::verify(curve, message, public_key, &signature)
+    }
+
+    // verify, validating the public key first unless the cache has already seen it
+    pub fn verify_with_cache(curve: &EllipticCurve, message: &[u8], public_key: &Point, signature: &EcdsaSignature, cache: &mut ValidatedKeyCache) -> Result<bool, String> {
+        cache.validate(curve, public_key)?;
+        EcdsaSignature::verify_validated_key(curve, message, public_key, signature)
+    }
+
+    // verify with fixed-base tables for G and for the signer's public key Q = q_table.base, so
+    // both u1*G and u2*Q skip the doublings; worthwhile for many signatures from one signer
+    pub fn verify_with_tables(curve: &EllipticCurve, message: &[u8], g_table: &PrecomputedBase, q_table: &PrecomputedBase, signature: &EcdsaSignature) -> Result<bool, String> {
+        if g_table.base != curve.g {
+            return Err("Generator table was not built for the curve's generator".to_string());
         }
+        curve.validate_public_key(&q_table.base)?;
+
+        let (u1, u2) = calculate_u1_u2(curve, message, signature)?;
+        let p = curve.add(&curve.mul_precomputed(g_table, u1.get_value())?, &curve.mul_precomputed(q_table, u2.get_value())?)?;
+        let x = p.x().ok_or("Invalid point generated in verification")?;
+
+        Ok(x.get_value() % &curve.n == signature.r)
+    }
+
+    // Recompute R' = u1*G + u2*Q without comparing it to r, for debugging interop mismatches
+    // a valid signature satisfies R'.x mod n == r
+    pub fn verify_recovering_point(curve: &EllipticCurve, message: &[u8], public_key: &Point, signature: &EcdsaSignature) -> Result<Point, String> {
+        let (u1, u2) = calculate_u1_u2(curve, message, signature)?;
+
+        curve.scalar_mul_double(&u1.into(), &curve.g, &u2.into(), public_key)
+             .map_err(|e| e.to_string())
+    }
+
+    // Recover the public key from a signature over an already computed digest, given which
+    // candidate R the signer's nonce point was: Q = r^-1 (s*R - e*G)
+    pub fn recover_public_key(curve: &EllipticCurve, digest: &[u8], signature: &EcdsaSignature, recovery_id: RecoveryId) -> Result<Point, String> {
+        if signature.r.is_zero() || signature.r >= curve.n || signature.s.is_zero() || signature.s >= curve.n {
+            return Err("Signature r and s must be in the range [1, n-1]".to_string());
+        }
+
+        let r_point = nonce_point(curve, signature, recovery_id)?;
+
+        let r_inv = Scalar::new(signature.r.clone(), curve.n.clone()).inverse()?;
+        let minus_e = Scalar::from_hash(digest, &curve.n).neg();
+        let u1 = minus_e.mul(&r_inv)?;
+        let u2 = Scalar::new(signature.s.clone(), curve.n.clone()).mul(&r_inv)?;
+
+        let public_key = curve.scalar_mul_double(&u1.into(), &curve.g, &u2.into(), &r_point)?;
+        if public_key.is_identity() {
+            return Err("Recovered public key is the point at infinity".to_string());
+        }
+        Ok(public_key)
+    }
+
+    // Verify a batch of (message, public key, signature, recovery id) items, true only if every
+    // signature is valid. The recovery id (from sign_recoverable) pins down the nonce point R, so
+    // with random 128-bit z_i every item folds into one multi-scalar multiplication,
+    // (sum z_i*u1_i)*G + sum z_i*u2_i*Q_i - sum z_i*R_i, which is the identity when all the
+    // equations u1*G + u2*Q = R hold and otherwise only with probability about 2^-128.
+    // A valid signature paired with the wrong recovery id counts as invalid.
+    // On failure this returns false without identifying which signature was invalid
+    pub fn verify_batch(curve: &EllipticCurve, items: &[(&[u8], &Point, &EcdsaSignature, RecoveryId)]) -> Result<bool, String> {
+        let z_bound = BigUint::from(1u32) << 128;
+        let mut g_coefficient = Scalar::new(BigUint::zero(), curve.n.clone());
+        let mut pairs = Vec::with_capacity(2 * items.len() + 1);
+
+        for (message, public_key, signature, recovery_id) in items {
+            curve.validate_public_key(public_key)?;
+            let (u1, u2) = calculate_u1_u2(curve, message, signature)?;
+            let r_point = match nonce_point(curve, signature, *recovery_id) {
+                Ok(r_point) => r_point,
+                Err(_) => return Ok(false),
+            };
+
+            let z = Scalar::new(generate_nonzero_random(&mut OsRng, &z_bound), curve.n.clone());
+            g_coefficient = g_coefficient.add(&z.mul(&u1)?)?;
+            pairs.push((z.mul(&u2)?.into(), (*public_key).clone()));
+            pairs.push((z.neg().into(), r_point));
+        }
+        pairs.push((g_coefficient.into(), curve.g.clone()));
+
+        let window = (pairs.len().ilog2() as usize).clamp(1, 16);
+        Ok(curve.pippenger(&pairs, window)?.is_identity())
+    }
+
+    // Cheap sanity check before verification: r must be the x-coordinate of some curve point,
+    // i.e. x^3 + ax + b is a quadratic residue mod p for x = r, or x = r + n if R.x overflowed n
+    pub fn r_is_plausible(&self, curve: &EllipticCurve) -> bool {
+        is_valid_abscissa(curve, &self.r) || is_valid_abscissa(curve, &(&self.r + &curve.n))
+    }
+
+    // Verify each (message, public key, signature) independently across the rayon thread pool
+    // results are in the same order as items
+    #[cfg(feature = "parallel")]
+    pub fn verify_par(curve: &EllipticCurve, items: &[(&[u8], &Point, &EcdsaSignature)]) -> Vec<Result<bool, String>> {
+        use rayon::prelude::*;
+
+        items.par_iter()
+             .map(|(message, public_key, signature)| EcdsaSignature::verify(curve, message, public_key, signature))
+             .collect()
+    }
+}
+
+impl<'a> EcdsaSigner<'a> {
+    pub fn new(curve: &'a EllipticCurve, private_key: &'a BigUint) -> Self {
+        EcdsaSigner { curve, private_key, hasher: Sha256::new() }
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    pub fn finalize(self) -> Result<EcdsaSignature, String> {
+        EcdsaSignature::sign_prehashed(self.curve, &self.hasher.finalize(), self.private_key)
     }
 }
 
 // Helper functions
-fn hash_message(message: &[u8]) -> BigUint {
+fn hash_message(message: &[u8]) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(message);
-    let hash_result = hasher.finalize();
-    BigUint::from_bytes_be(&hash_result)
+    hasher.finalize().into()
+}
+
+// one value in the layout of OpenSSL's -text output: the label on its own line, then the bytes
+// as colon-separated hex, 15 per line and indented by 4 spaces
+fn write_openssl_hex(out: &mut dyn Write, label: &str, bytes: &[u8]) -> Result<(), String> {
+    writeln!(out, "{}:", label).map_err(|e| e.to_string())?;
+    let lines: Vec<String> = bytes.chunks(15)
+                                  .map(|chunk| chunk.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(":"))
+                                  .collect();
+    writeln!(out, "    {}", lines.join(":\n    ")).map_err(|e| e.to_string())
 }
 
-fn generate_nonzero_random(rng: &mut OsRng, p: &BigUint) -> BigUint {
+// big-endian bytes of a bignum as OpenSSL prints them, with a leading 00 when the top bit is set
+fn openssl_bn_bytes(value: &BigUint) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    if bytes[0] & 0x80 != 0 {
+        [&[0u8][..], &bytes].concat()
+    } else {
+        bytes
+    }
+}
+
+fn generate_nonzero_random<R: RngCore + CryptoRng>(rng: &mut R, n: &BigUint) -> BigUint {
     loop {
-        let k = rng.gen_biguint_below(p);
+        let k = rng.gen_biguint_below(n);
         if k != BigUint::zero() {
             return k;
         }
     }
 }
 
-fn calculate_s_field(hash_field: &FiniteField, r_field: &FiniteField, private_key_field: &FiniteField, k: &BigUint, p: &BigUint) -> Result<FiniteField, String> {
-    let k_field = FiniteField::new(k.clone(), p.clone());
-    hash_field.add(&r_field.mul(private_key_field)?)
-             .and_then(|num| num.div(&k_field))
-             .map_err(|e| e.to_string())
+// s = (hash + r * private_key) / k mod n
+fn calculate_s(hash: &Scalar, r: &Scalar, private_key: &Scalar, k: &Scalar) -> Result<Scalar, String> {
+    hash.add(&r.mul(private_key)?)
+        .and_then(|num| num.mul(&k.inverse()?))
+        .map_err(|e| e.to_string())
+}
+
+// x < p and x^3 + ax + b is zero or a quadratic residue mod p
+fn is_valid_abscissa(curve: &EllipticCurve, x: &BigUint) -> bool {
+    if x >= &curve.p {
+        return false;
+    }
+    let x_field = FiniteField::new(x.clone(), curve.p.clone());
+    let right_side = (|| -> Result<FiniteField, &'static str> {
+        let x_cubed = x_field.mul(&x_field)?.mul(&x_field)?;
+        x_cubed.add(&curve.a.mul(&x_field)?)?.add(&curve.b)
+    })();
+    match right_side {
+        Ok(value) => value.legendre_symbol() >= 0,
+        Err(_) => false,
+    }
+}
+
+// the nonce point R named by a recovery id: R.x = r, or r + n when bit 1 says R.x overflowed n,
+// and bit 0 is the parity of R.y
+fn nonce_point(curve: &EllipticCurve, signature: &EcdsaSignature, recovery_id: RecoveryId) -> Result<Point, String> {
+    let x = if recovery_id.to_u8() & 2 != 0 { &signature.r + &curve.n } else { signature.r.clone() };
+    if x >= curve.p {
+        return Err("Recovered R.x is not less than p".to_string());
+    }
+    let (even, odd) = curve.lift_x(&FiniteField::new(x, curve.p.clone()))?;
+    Ok(if recovery_id.to_u8() & 1 == 0 { even } else { odd })
 }
 
+// u1 = hash / s mod n, u2 = r / s mod n
+fn calculate_u1_u2(curve: &EllipticCurve, message: &[u8], signature: &EcdsaSignature) -> Result<(Scalar, Scalar), String> {
+    if signature.r.is_zero() || signature.r >= curve.n || signature.s.is_zero() || signature.s >= curve.n {
+        return Err("Signature r and s must be in the range [1, n-1]".to_string());
+    }
+
+    let hash = Scalar::from_hash(&hash_message(message), &curve.n);
+
+    let w = Scalar::new(signature.s.clone(), curve.n.clone()).inverse()
+                                                              .map_err(|e| e.to_string())?;
+
+    let u1 = hash.mul(&w)?;
+    let u2 = Scalar::new(signature.r.clone(), curve.n.clone()).mul(&w)?;
+    Ok((u1, u2))
+}
 
 // Test cases for EcdsaKeyPair and EcdsaSignature
 #[cfg(test)]
 mod tests {
     use super::*;
-    use num_bigint::ToBigUint;
-    use crate::ecdsa;
+    use num_traits::Num;
+    use crate::scalar::bits2int;
+    use crate::secp256k1::Secp256k1;
 
     #[test]
     fn test_sign_normal_operation() {
@@ -129,6 +498,7 @@ mod tests {
                 FiniteField::new(g_x, p.clone()),
                 FiniteField::new(g_y, p.clone())
             ),
+            n: BigUint::from_str_radix("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", 16).unwrap(),
         };
 
         let key_pair = EcdsaKeyPair::generate(&curve);
@@ -139,7 +509,7 @@ mod tests {
         assert!(signature_result.is_ok(), "Failed to sign message");
         let signature = signature_result.unwrap();
 
-        // Optionally, you can add more checks here, e.g., on the structure of the signature
+        assert!(signature.r < curve.n && signature.s < curve.n);
     }
 
     #[test]
@@ -159,6 +529,7 @@ mod tests {
                 FiniteField::new(g_x, p.clone()),
                 FiniteField::new(g_y, p.clone())
             ),
+            n: BigUint::from_str_radix("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", 16).unwrap(),
         };
 
         // Generate a key pair
@@ -176,4 +547,686 @@ mod tests {
         // Assert that the signature is valid
         assert!(is_valid, "The signature should be valid.");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sign_private_key_range() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let message = "test message".as_bytes();
+
+        // d = 0 and d = n are outside [1, n-1]
+        assert!(EcdsaSignature::sign(&curve, message, &BigUint::from(0u32)).is_err());
+        assert!(EcdsaSignature::sign(&curve, message, &curve.n).is_err());
+
+        // d = 1 is the smallest valid private key
+        let signature = EcdsaSignature::sign(&curve, message, &BigUint::from(1u32)).unwrap();
+        assert!(EcdsaSignature::verify(&curve, message, &curve.g, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_sign_hash_larger_than_modulus() {
+        // y^2 = x^3 + 2x + 2 mod 17, every SHA-256 digest exceeds p and n here
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            p: p.clone(),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+        };
+        let message = "test message".as_bytes();
+        assert!(BigUint::from_bytes_be(&hash_message(message)) > curve.p);
+
+        let signature = EcdsaSignature::sign(&curve, message, &BigUint::from(7u32));
+        assert!(signature.is_ok(), "Signing must not panic or fail when the digest exceeds p");
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_par_matches_sequential() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<EllipticCurve>();
+
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let messages: Vec<Vec<u8>> = (0..16u32).map(|i| format!("message {}", i).into_bytes()).collect();
+        let mut signatures: Vec<EcdsaSignature> = messages.iter()
+            .map(|m| EcdsaSignature::sign(&curve, m, &key_pair.private_key).unwrap())
+            .collect();
+
+        // tamper with one signature
+        signatures[5].s = (&signatures[5].s + 1u32) % &curve.n;
+
+        let items: Vec<(&[u8], &Point, &EcdsaSignature)> = messages.iter()
+            .zip(signatures.iter())
+            .map(|(m, sig)| (m.as_slice(), &key_pair.public_key, sig))
+            .collect();
+
+        let sequential: Vec<Result<bool, String>> = items.iter()
+            .map(|(m, q, sig)| EcdsaSignature::verify(&curve, m, q, sig))
+            .collect();
+        let parallel = EcdsaSignature::verify_par(&curve, &items);
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel[5], Ok(false));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_par_100_signatures() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pairs: Vec<EcdsaKeyPair> = (0..4).map(|_| EcdsaKeyPair::generate(&curve)).collect();
+        let messages: Vec<Vec<u8>> = (0..100u32).map(|i| format!("parallel message {}", i).into_bytes()).collect();
+        let mut signatures: Vec<EcdsaSignature> = messages.iter()
+            .enumerate()
+            .map(|(i, m)| EcdsaSignature::sign(&curve, m, &key_pairs[i % 4].private_key).unwrap())
+            .collect();
+        signatures[63].s = (&signatures[63].s + 1u32) % &curve.n;
+
+        let items: Vec<(&[u8], &Point, &EcdsaSignature)> = messages.iter()
+            .enumerate()
+            .map(|(i, m)| (m.as_slice(), &key_pairs[i % 4].public_key, &signatures[i]))
+            .collect();
+        let sequential: Vec<Result<bool, String>> = items.iter()
+            .map(|(m, q, sig)| EcdsaSignature::verify(&curve, m, q, sig))
+            .collect();
+        let parallel = EcdsaSignature::verify_par(&curve, &items);
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel.iter().filter(|result| *result == &Ok(false)).count(), 1);
+        assert_eq!(parallel[63], Ok(false));
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pairs: Vec<EcdsaKeyPair> = (0..3).map(|_| EcdsaKeyPair::generate(&curve)).collect();
+        let messages: Vec<Vec<u8>> = (0..3u32).map(|i| format!("batch message {}", i).into_bytes()).collect();
+        let mut signatures: Vec<(EcdsaSignature, RecoveryId)> = messages.iter()
+            .zip(key_pairs.iter())
+            .map(|(m, kp)| EcdsaSignature::sign_recoverable(&curve, m, &kp.private_key).unwrap())
+            .collect();
+
+        let items: Vec<(&[u8], &Point, &EcdsaSignature, RecoveryId)> = messages.iter()
+            .zip(key_pairs.iter())
+            .zip(signatures.iter())
+            .map(|((m, kp), (sig, id))| (m.as_slice(), &kp.public_key, sig, *id))
+            .collect();
+        assert_eq!(EcdsaSignature::verify_batch(&curve, &items), Ok(true));
+
+        // tamper with one signature
+        signatures[1].0.s = (&signatures[1].0.s + 1u32) % &curve.n;
+        let items: Vec<(&[u8], &Point, &EcdsaSignature, RecoveryId)> = messages.iter()
+            .zip(key_pairs.iter())
+            .zip(signatures.iter())
+            .map(|((m, kp), (sig, id))| (m.as_slice(), &kp.public_key, sig, *id))
+            .collect();
+        assert_eq!(EcdsaSignature::verify_batch(&curve, &items), Ok(false));
+    }
+
+    #[test]
+    fn test_verify_batch_combined_check() {
+        use crate::op_counter::{op_counts, reset_op_counts};
+
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pairs: Vec<EcdsaKeyPair> = (0..4).map(|_| EcdsaKeyPair::generate(&curve)).collect();
+        let messages: Vec<Vec<u8>> = (0..16u32).map(|i| format!("batch message {}", i).into_bytes()).collect();
+        // nonces k = 1..16 fix R = k*G, a mix of even and odd R.y that is not normalized away
+        let mut signatures: Vec<(EcdsaSignature, RecoveryId)> = messages.iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let k = BigUint::from(i as u32 + 1);
+                let signature = EcdsaSignature::sign_with_nonce(&curve, m, &key_pairs[i % 4].private_key, &k).unwrap();
+                let even = curve.mul_generator(&k).unwrap().y_parity().unwrap();
+                (signature, RecoveryId::new(u8::from(!even)).unwrap())
+            })
+            .collect();
+        let odd = signatures.iter().filter(|(_, id)| id.to_u8() == 1).count();
+        assert!(odd > 0 && odd < signatures.len(), "{} of 16 with odd R.y", odd);
+
+        // a single multi-scalar multiplication, about bits(n) doublings rather than that many per item
+        reset_op_counts();
+        let items: Vec<(&[u8], &Point, &EcdsaSignature, RecoveryId)> = messages.iter()
+            .enumerate()
+            .map(|(i, m)| (m.as_slice(), &key_pairs[i % 4].public_key, &signatures[i].0, signatures[i].1))
+            .collect();
+        assert_eq!(EcdsaSignature::verify_batch(&curve, &items), Ok(true));
+        assert!(op_counts().doublings < 2 * curve.n.bits(), "{:?}", op_counts());
+
+        // the wrong parity names -R, so a valid signature no longer passes
+        let mut flipped = items.clone();
+        flipped[5].3 = RecoveryId::new(flipped[5].3.to_u8() ^ 1).unwrap();
+        assert_eq!(EcdsaSignature::verify_batch(&curve, &flipped), Ok(false));
+
+        // a tampered signature fails the combined check
+        signatures[2].0.s = (&signatures[2].0.s + 1u32) % &curve.n;
+        let items: Vec<(&[u8], &Point, &EcdsaSignature, RecoveryId)> = messages.iter()
+            .enumerate()
+            .map(|(i, m)| (m.as_slice(), &key_pairs[i % 4].public_key, &signatures[i].0, signatures[i].1))
+            .collect();
+        assert_eq!(EcdsaSignature::verify_batch(&curve, &items), Ok(false));
+        assert_eq!(EcdsaSignature::verify_batch(&curve, &[]), Ok(true));
+    }
+
+    #[test]
+    fn test_verify_with_tables() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let g_table = curve.precompute_base(&curve.g).unwrap();
+        let q_table = curve.precompute_base(&key_pair.public_key).unwrap();
+
+        for i in 0..3u32 {
+            let message = format!("table message {}", i).into_bytes();
+            let mut signature = EcdsaSignature::sign(&curve, &message, &key_pair.private_key).unwrap();
+            assert_eq!(EcdsaSignature::verify_with_tables(&curve, &message, &g_table, &q_table, &signature), Ok(true));
+            assert_eq!(EcdsaSignature::verify_with_tables(&curve, b"other message", &g_table, &q_table, &signature),
+                       EcdsaSignature::verify(&curve, b"other message", &key_pair.public_key, &signature));
+
+            signature.s = (&signature.s + 1u32) % &curve.n;
+            assert_eq!(EcdsaSignature::verify_with_tables(&curve, &message, &g_table, &q_table, &signature), Ok(false));
+            assert_eq!(EcdsaSignature::verify(&curve, &message, &key_pair.public_key, &signature), Ok(false));
+        }
+
+        // the tables must be passed in the right order
+        let signature = EcdsaSignature::sign(&curve, b"swapped", &key_pair.private_key).unwrap();
+        assert!(EcdsaSignature::verify_with_tables(&curve, b"swapped", &q_table, &g_table, &signature).is_err());
+    }
+
+    #[test]
+    fn test_verify_recovering_point() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let message = "Hello, world".as_bytes();
+        let signature = EcdsaSignature::sign(&curve, message, &key_pair.private_key).unwrap();
+
+        let r_point = EcdsaSignature::verify_recovering_point(&curve, message, &key_pair.public_key, &signature).unwrap();
+        match r_point {
+            Point::Coor(x, _) => assert_eq!(x.get_value() % &curve.n, signature.r),
+            Point::Identity => panic!("R' should not be the identity for a valid signature"),
+        }
+    }
+
+    #[test]
+    fn test_sign_verbose_trace_matches_signature() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let message = "Hello, world".as_bytes();
+
+        let mut out = Vec::new();
+        let signature = EcdsaSignature::sign_verbose(&curve, message, &key_pair.private_key, &mut out).unwrap();
+        let trace = String::from_utf8(out).unwrap();
+
+        // a label line, then the indented colon-separated bytes
+        let traced_value = |label: &str| -> BigUint {
+            let hex: String = trace.lines()
+                                   .skip_while(|l| *l != format!("{}:", label))
+                                   .skip(1)
+                                   .take_while(|l| l.starts_with("    "))
+                                   .flat_map(|l| l.trim().split(':').filter(|byte| !byte.is_empty()))
+                                   .collect();
+            BigUint::from_str_radix(&hex, 16).unwrap()
+        };
+        assert_eq!(traced_value("r"), signature.r);
+        assert_eq!(traced_value("s"), signature.s);
+        assert_eq!(traced_value("hash"), BigUint::from_bytes_be(&hash_message(message)));
+        assert!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_openssl_hex_layout() {
+        // 2^255 + 1 has its top bit set, so it gets a 00 byte and wraps after 15 bytes
+        let mut out = Vec::new();
+        write_openssl_hex(&mut out, "r", &openssl_bn_bytes(&((BigUint::from(1u32) << 255) + 1u32))).unwrap();
+        write_openssl_hex(&mut out, "k", &openssl_bn_bytes(&BigUint::from(0x7fu32))).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "r:\n    \
+            00:80:00:00:00:00:00:00:00:00:00:00:00:00:00:\n    \
+            00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:\n    \
+            00:00:01\n\
+            k:\n    7f\n");
+    }
+
+    #[test]
+    fn test_sign_prehashed_matches_sign() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let message = "Hello, world".as_bytes();
+        let k = BigUint::from(123456789u64);
+
+        // a digest computed by the caller, signed with the same nonce as the public message path
+        let digest = Sha256::digest(message);
+        let prehashed = EcdsaSignature::sign_prehashed_with_nonce(&curve, &digest, &EcdsaSignature::private_scalar(&curve, &key_pair.private_key).unwrap(), &k, None).unwrap().unwrap().0;
+        let hashed = EcdsaSignature::sign_with_nonce(&curve, message, &key_pair.private_key, &k).unwrap();
+        assert_eq!((&prehashed.r, &prehashed.s), (&hashed.r, &hashed.s));
+
+        // with fresh nonces the signatures differ, but both verify against the message
+        let signed = EcdsaSignature::sign(&curve, message, &key_pair.private_key).unwrap();
+        let signed_prehashed = EcdsaSignature::sign_prehashed(&curve, &digest, &key_pair.private_key).unwrap();
+        assert!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &signed).unwrap());
+        assert!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &signed_prehashed).unwrap());
+    }
+
+    #[test]
+    fn test_generate_from_seed() {
+        let curve = Secp256k1::new().elliptic_curve;
+
+        let key_pair = EcdsaKeyPair::generate_from_seed(&curve, b"test seed").unwrap();
+        let again = EcdsaKeyPair::generate_from_seed(&curve, b"test seed").unwrap();
+        assert_eq!(key_pair.private_key, again.private_key);
+        assert_eq!(key_pair.public_key, again.public_key);
+        assert_eq!(curve.scalar_mul(&curve.g, &key_pair.private_key), Ok(key_pair.public_key.clone()));
+
+        let other = EcdsaKeyPair::generate_from_seed(&curve, b"other seed").unwrap();
+        assert_ne!(key_pair.private_key, other.private_key);
+        assert_ne!(key_pair.public_key, other.public_key);
+    }
+
+    #[test]
+    fn test_sign_with_nonce_vector() {
+        // RFC 6979 secp256k1/SHA-256 vector used by Bitcoin implementations: d = 1, "Satoshi Nakamoto"
+        let curve = Secp256k1::new().elliptic_curve;
+        let k = BigUint::from_str_radix("8F8A276C19F4149656B280621E358CCE24F5F52542772691EE69063B74F15D15", 16).unwrap();
+        let signature = EcdsaSignature::sign_with_nonce(&curve, b"Satoshi Nakamoto", &BigUint::from(1u32), &k).unwrap();
+
+        assert_eq!(signature.r, BigUint::from_str_radix("934B1EA10A4B3C1757E2B0C017D0B6143CE3C9A7E6A4A49860D7A6AB210EE3D8", 16).unwrap());
+        // the published s is the low-S form n - s
+        let low_s = BigUint::from_str_radix("2442CE9D2B916064108014783E923EC36B49743E2FFA1C4496F01A512AAFD9E5", 16).unwrap();
+        assert_eq!(signature.s, &curve.n - low_s);
+        assert!(EcdsaSignature::verify(&curve, b"Satoshi Nakamoto", &curve.g, &signature).unwrap());
+
+        assert!(EcdsaSignature::sign_with_nonce(&curve, b"Satoshi Nakamoto", &BigUint::from(1u32), &BigUint::zero()).is_err());
+        assert!(EcdsaSignature::sign_with_nonce(&curve, b"Satoshi Nakamoto", &BigUint::from(1u32), &curve.n).is_err());
+    }
+
+    #[test]
+    fn test_compact_bytes() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let signature = EcdsaSignature { r: BigUint::from(0x0102u32), s: &curve.n - 1u32 };
+
+        let bytes = signature.to_compact_bytes(&curve.n).unwrap();
+        assert_eq!(&bytes[..30], &[0u8; 30]);
+        assert_eq!(&bytes[30..32], &[0x01, 0x02]);
+        assert_eq!(&bytes[32..], &signature.s.to_bytes_be()[..]);
+
+        let parsed = EcdsaSignature::from_compact_bytes(&bytes, &curve.n).unwrap();
+        assert_eq!((parsed.r, parsed.s), (signature.r, signature.s));
+    }
+
+    #[test]
+    fn test_compact_bytes_rejects_out_of_range() {
+        let curve = Secp256k1::new().elliptic_curve;
+
+        // s = n
+        let mut bytes = [0u8; 64];
+        bytes[31] = 1;
+        bytes[32..].copy_from_slice(&curve.n.to_bytes_be());
+        assert!(EcdsaSignature::from_compact_bytes(&bytes, &curve.n).is_err());
+
+        // r = 0
+        let zero_r = EcdsaSignature { r: BigUint::zero(), s: BigUint::from(1u32) }.to_compact_bytes(&curve.n).unwrap();
+        assert!(EcdsaSignature::from_compact_bytes(&zero_r, &curve.n).is_err());
+    }
+
+    #[test]
+    fn test_compact_bytes_rejects_wide_values() {
+        // a P-384 signature has 48-byte halves, which the 64-byte encoding cannot hold
+        let p384 = EllipticCurve::from_named("P-384").unwrap();
+        let key_pair = EcdsaKeyPair::generate_from_seed(&p384, b"compact").unwrap();
+        let signature = EcdsaSignature::sign(&p384, b"message", &key_pair.private_key).unwrap();
+        assert!(signature.to_compact_bytes(&p384.n).is_err());
+
+        let curve = Secp256k1::new().elliptic_curve;
+        let unreduced = EcdsaSignature { r: BigUint::from(1u32), s: curve.n.clone() };
+        assert!(unreduced.to_compact_bytes(&curve.n).is_err());
+    }
+
+    #[test]
+    fn test_sign_many() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let messages: [&[u8]; 3] = [b"first", b"second", b"first"];
+
+        let signatures = EcdsaSignature::sign_many(&curve, &messages, &key_pair.private_key).unwrap();
+        assert_eq!(signatures.len(), messages.len());
+        for (message, signature) in messages.iter().zip(&signatures) {
+            assert!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, signature).unwrap());
+        }
+        // fresh nonces, even for a repeated message
+        assert_ne!(signatures[0].r, signatures[2].r);
+
+        assert!(EcdsaSignature::sign_many(&curve, &messages, &curve.n).is_err());
+    }
+
+    #[test]
+    fn test_bits2int_truncates_long_digest() {
+        // n = 19 has 5 bits, so only the leftmost 5 bits of the digest are kept
+        let digest = [0b1011_0110u8, 0xFF];
+        assert_eq!(bits2int(&digest, &BigUint::from(19u32)), BigUint::from(0b10110u32));
+    }
+
+    #[test]
+    fn test_bits2int_keeps_leading_zero_bytes() {
+        // a 160-bit n keeps the first 20 bytes of the 32-byte digest, zero first byte included;
+        // truncating the minimal BigUint representation would shift in one byte too many
+        let mut digest = [0xCDu8; 32];
+        digest[0] = 0x00;
+        digest[1] = 0xAB;
+        let n = (BigUint::from(1u32) << 159u32) + BigUint::from(1u32);
+        assert_eq!(bits2int(&digest, &n), BigUint::from_bytes_be(&digest[..20]));
+
+        // with n = 19 the leftmost 5 bits all fall inside the zero byte
+        assert_eq!(bits2int(&digest, &BigUint::from(19u32)), BigUint::zero());
+    }
+
+    #[test]
+    fn test_r_is_plausible() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let signature = EcdsaSignature::sign(&curve, "Hello, world".as_bytes(), &key_pair.private_key).unwrap();
+        assert!(signature.r_is_plausible(&curve));
+
+        // 7^3 + 7 is a non-residue, but (7 + n)^3 + 7 is a residue
+        let overflowed = EcdsaSignature { r: BigUint::from(7u32), s: BigUint::from(1u32) };
+        assert!(overflowed.r_is_plausible(&curve));
+
+        // neither 5 nor 5 + n is the x-coordinate of a secp256k1 point
+        let implausible = EcdsaSignature { r: BigUint::from(5u32), s: BigUint::from(1u32) };
+        assert!(!implausible.r_is_plausible(&curve));
+    }
+
+    #[test]
+    fn test_sign_verify_round_trip_random() {
+        use rand::Rng;
+
+        let curve = Secp256k1::new().elliptic_curve;
+        let mut rng = OsRng;
+
+        for _ in 0..8 {
+            let private_key = rng.gen_biguint_range(&BigUint::from(1u32), &curve.n);
+            let public_key = curve.scalar_mul(&curve.g, &private_key).unwrap();
+            let message: Vec<u8> = (0..rng.gen_range(0..64)).map(|_| rng.gen()).collect();
+
+            let signature = EcdsaSignature::sign(&curve, &message, &private_key).unwrap();
+            assert!(EcdsaSignature::verify(&curve, &message, &public_key, &signature).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_mutated_message_random() {
+        use rand::Rng;
+
+        let curve = Secp256k1::new().elliptic_curve;
+        let mut rng = OsRng;
+
+        for _ in 0..8 {
+            let private_key = rng.gen_biguint_range(&BigUint::from(1u32), &curve.n);
+            let public_key = curve.scalar_mul(&curve.g, &private_key).unwrap();
+            let message: Vec<u8> = (0..rng.gen_range(1..64)).map(|_| rng.gen()).collect();
+
+            let signature = EcdsaSignature::sign(&curve, &message, &private_key).unwrap();
+
+            let mut mutated = message.clone();
+            let index = rng.gen_range(0..mutated.len());
+            mutated[index] ^= rng.gen_range(1..=255u8);
+            assert!(!EcdsaSignature::verify(&curve, &mutated, &public_key, &signature).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_verify_with_cache() {
+        use crate::op_counter::{op_counts, reset_op_counts, OpCounts};
+
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let mut cache = ValidatedKeyCache::new();
+        let counted = |f: &mut dyn FnMut()| -> OpCounts {
+            reset_op_counts();
+            f();
+            op_counts()
+        };
+        let plus = |a: OpCounts, b: OpCounts| OpCounts { additions: a.additions + b.additions, doublings: a.doublings + b.doublings };
+
+        // the first signature pays for n*Q on top of u1*G + u2*Q, the second only for u1*G + u2*Q
+        for (message, validated) in [("first".as_bytes(), true), ("second".as_bytes(), false)] {
+            let signature = EcdsaSignature::sign(&curve, message, &key_pair.private_key).unwrap();
+            let verification = counted(&mut || { EcdsaSignature::verify_recovering_point(&curve, message, &key_pair.public_key, &signature).unwrap(); });
+            let validation = counted(&mut || { curve.scalar_mul(&key_pair.public_key, &curve.n).unwrap(); });
+            let expected = if validated { plus(verification, validation) } else { verification };
+
+            let cached = counted(&mut || assert!(EcdsaSignature::verify_with_cache(&curve, message, &key_pair.public_key, &signature, &mut cache).unwrap()));
+            assert_eq!(cached, expected, "{}", String::from_utf8_lossy(message));
+        }
+
+        let signature = EcdsaSignature::sign(&curve, "first".as_bytes(), &key_pair.private_key).unwrap();
+        assert!(!EcdsaSignature::verify_with_cache(&curve, "tampered".as_bytes(), &key_pair.public_key, &signature, &mut cache).unwrap());
+        assert!(EcdsaSignature::verify_with_cache(&curve, "first".as_bytes(), &Point::Identity, &signature, &mut cache).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_zero_r_or_s() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let message = "Hello, world".as_bytes();
+        let signature = EcdsaSignature::sign(&curve, message, &key_pair.private_key).unwrap();
+
+        let zero_r = EcdsaSignature { r: BigUint::zero(), s: signature.s.clone() };
+        assert!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &zero_r).is_err());
+
+        let zero_s = EcdsaSignature { r: signature.r.clone(), s: BigUint::zero() };
+        assert!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &zero_s).is_err());
+
+        let large_s = EcdsaSignature { r: signature.r.clone(), s: curve.n.clone() };
+        assert!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &large_s).is_err());
+    }
+
+    #[test]
+    fn test_sign_retries_zero_r() {
+        // y^2 = x^3 + 2x + 2 mod 17, 7G = (0, 6) so k = 7 gives r = 0
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            p: p.clone(),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+        };
+        let digest = hash_message("test message".as_bytes());
+        let private_key = BigUint::from(7u32);
+
+        let result = EcdsaSignature::sign_prehashed_with_nonce(&curve, &digest, &EcdsaSignature::private_scalar(&curve, &private_key).unwrap(), &BigUint::from(7u32), None);
+        assert!(matches!(result, Ok(None)));
+
+        for _ in 0..50 {
+            let signature = EcdsaSignature::sign_prehashed(&curve, &digest, &private_key).unwrap();
+            assert!(!signature.r.is_zero() && !signature.s.is_zero());
+        }
+    }
+
+    #[test]
+    fn test_generate_with_seeded_rng() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let curve = Secp256k1::new().elliptic_curve;
+        let first = EcdsaKeyPair::generate_with_rng(&curve, &mut ChaCha20Rng::seed_from_u64(42));
+        let second = EcdsaKeyPair::generate_with_rng(&curve, &mut ChaCha20Rng::seed_from_u64(42));
+        assert_eq!(first.private_key, second.private_key);
+        assert_eq!(first.public_key, second.public_key);
+
+        assert!(!first.private_key.is_zero() && first.private_key < curve.n);
+        assert_eq!(curve.scalar_mul(&curve.g, &first.private_key), Ok(first.public_key));
+
+        let other = EcdsaKeyPair::generate_with_rng(&curve, &mut ChaCha20Rng::seed_from_u64(43));
+        assert_ne!(other.private_key, second.private_key);
+    }
+
+    #[test]
+    fn test_verify_rs_matches_verify() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let message = "Hello, world".as_bytes();
+        let signature = EcdsaSignature::sign(&curve, message, &key_pair.private_key).unwrap();
+
+        assert_eq!(EcdsaSignature::verify_rs(&curve, message, &key_pair.public_key, &signature.r, &signature.s), Ok(true));
+
+        let tampered_s = &signature.s + 1u32;
+        let tampered = EcdsaSignature { r: signature.r.clone(), s: tampered_s.clone() };
+        assert_eq!(
+            EcdsaSignature::verify_rs(&curve, message, &key_pair.public_key, &signature.r, &tampered_s),
+            EcdsaSignature::verify(&curve, message, &key_pair.public_key, &tampered)
+        );
+    }
+
+    #[test]
+    fn test_sign_verify_with_zero_truncated_hash() {
+        // y^2 = x^3 + 2x + 2 mod 17, n = 19 keeps only the leftmost 5 bits of the digest
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            p: p.clone(),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+        };
+
+        // SHA-256("message 35") = 006c0843..., so e = 0
+        let message = "message 35".as_bytes();
+        assert!(bits2int(&hash_message(message), &curve.n).is_zero());
+
+        let private_key = BigUint::from(3u32);
+        let public_key = curve.scalar_mul(&curve.g, &private_key).unwrap();
+
+        // with e = 0, s = r*d/k: k = 2 gives R = (6, 3), r = 6 and s = 6*3/2 = 9 mod 19
+        let signature = EcdsaSignature::sign_prehashed_with_nonce(&curve, &hash_message(message), &EcdsaSignature::private_scalar(&curve, &private_key).unwrap(), &BigUint::from(2u32), None).unwrap().unwrap().0;
+        assert_eq!((signature.r.clone(), signature.s.clone()), (BigUint::from(6u32), BigUint::from(9u32)));
+        assert_eq!(EcdsaSignature::verify(&curve, message, &public_key, &signature), Ok(true));
+
+        for _ in 0..20 {
+            let signature = EcdsaSignature::sign(&curve, message, &private_key).unwrap();
+            assert_eq!(EcdsaSignature::verify(&curve, message, &public_key, &signature), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_sign_verify_truncates_digest_on_toy_curve() {
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            p: p.clone(),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+        };
+
+        // SHA-256("sample") = af2b..., leftmost 5 bits 10101 = 21, so e = 2 mod 19;
+        // reducing the whole 256-bit digest mod 19 would give 13 instead
+        let message = "sample".as_bytes();
+        let digest = hash_message(message);
+        assert_eq!(bits2int(&digest, &curve.n), BigUint::from(21u32));
+        assert_eq!(BigUint::from_bytes_be(&digest) % &curve.n, BigUint::from(13u32));
+
+        // d = 3, k = 2: r = 6 and s = (2 + 6*3)/2 = 10 mod 19 (13 would have given s = 6)
+        let private_key = BigUint::from(3u32);
+        let public_key = curve.scalar_mul(&curve.g, &private_key).unwrap();
+        let signature = EcdsaSignature::sign_prehashed_with_nonce(&curve, &digest, &EcdsaSignature::private_scalar(&curve, &private_key).unwrap(), &BigUint::from(2u32), None).unwrap().unwrap().0;
+        assert_eq!((signature.r.clone(), signature.s.clone()), (BigUint::from(6u32), BigUint::from(10u32)));
+        assert_eq!(EcdsaSignature::verify(&curve, message, &public_key, &signature), Ok(true));
+
+        let reduced = EcdsaSignature { r: BigUint::from(6u32), s: BigUint::from(6u32) };
+        assert_eq!(EcdsaSignature::verify(&curve, message, &public_key, &reduced), Ok(false));
+    }
+
+    #[test]
+    fn test_verify_compares_r_mod_n() {
+        // y^2 = x^3 + 7 mod 17, G = (5, 8) of order 3, so R.x = 5 differs from r = 5 mod 3 = 2
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(0u32), p.clone()),
+            b: FiniteField::new(BigUint::from(7u32), p.clone()),
+            p: p.clone(),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(8u32), p.clone())),
+            n: BigUint::from(3u32),
+        };
+
+        // d = 1, k = 1 and SHA-256("hello") = 2cf2... gives e = 0: r = 2, s = (0 + 2*1)/1 = 2
+        let message = "hello".as_bytes();
+        let signature = EcdsaSignature::sign_prehashed_with_nonce(&curve, &hash_message(message), &EcdsaSignature::private_scalar(&curve, &BigUint::from(1u32)).unwrap(), &BigUint::from(1u32), None).unwrap().unwrap().0;
+        assert_eq!((signature.r.clone(), signature.s.clone()), (BigUint::from(2u32), BigUint::from(2u32)));
+
+        assert_eq!(EcdsaSignature::verify(&curve, message, &curve.g, &signature), Ok(true));
+        // R = G = (5, 8) has an even y and R.x = 5 overflowed n = 3, so the recovery id is 2
+        assert_eq!(EcdsaSignature::verify_batch(&curve, &[(message, &curve.g, &signature, RecoveryId::new(2).unwrap())]), Ok(true));
+    }
+
+    #[test]
+    fn test_verify_rejects_identity_public_key() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let message = "Hello, world".as_bytes();
+        let signature = EcdsaSignature::sign(&curve, message, &key_pair.private_key).unwrap();
+
+        assert!(EcdsaSignature::verify(&curve, message, &Point::Identity, &signature).is_err());
+    }
+
+    #[test]
+    fn test_recover_public_key() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let digest = hash_message("Hello, world".as_bytes());
+        let signature = EcdsaSignature::sign_prehashed(&curve, &digest, &key_pair.private_key).unwrap();
+
+        // exactly one of the recovery ids with R.x < n gives back the signer's key
+        let recovered: Vec<Point> = (0..2)
+            .filter_map(|id| EcdsaSignature::recover_public_key(&curve, &digest, &signature, RecoveryId::new(id).unwrap()).ok())
+            .collect();
+        assert_eq!(recovered.iter().filter(|point| **point == key_pair.public_key).count(), 1);
+    }
+
+    #[test]
+    fn test_sign_recoverable() {
+        let curve = Secp256k1::new().elliptic_curve;
+        for _ in 0..4 {
+            let key_pair = EcdsaKeyPair::generate(&curve);
+            let message = "recoverable message".as_bytes();
+            let (signature, recovery_id) = EcdsaSignature::sign_recoverable(&curve, message, &key_pair.private_key).unwrap();
+
+            assert_eq!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &signature), Ok(true));
+            let digest = hash_message(message);
+            assert_eq!(EcdsaSignature::recover_public_key(&curve, &digest, &signature, recovery_id), Ok(key_pair.public_key.clone()));
+
+            // the other parity gives a different key
+            let flipped = RecoveryId::new(recovery_id.to_u8() ^ 1).unwrap();
+            assert_ne!(EcdsaSignature::recover_public_key(&curve, &digest, &signature, flipped), Ok(key_pair.public_key));
+        }
+    }
+
+    #[test]
+    fn test_sign_full() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let message = "threshold message".as_bytes();
+        let (signature, r_point) = EcdsaSignature::sign_full(&curve, message, &key_pair.private_key).unwrap();
+
+        assert_eq!(curve.is_on_curve(&r_point), Ok(true));
+        let (x, _) = r_point.coords().unwrap();
+        assert_eq!(x % &curve.n, signature.r);
+        assert_eq!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &signature), Ok(true));
+
+        // R is the point verification recomputes
+        assert_eq!(EcdsaSignature::verify_recovering_point(&curve, message, &key_pair.public_key, &signature), Ok(r_point));
+    }
+
+    #[test]
+    fn test_streaming_signer() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let key_pair = EcdsaKeyPair::generate(&curve);
+        let message = b"a message long enough to be fed to the signer in several chunks";
+
+        let mut signer = EcdsaSigner::new(&curve, &key_pair.private_key);
+        for chunk in [&message[..10], &message[10..40], &message[40..]] {
+            signer.update(chunk);
+        }
+        let signature = signer.finalize().unwrap();
+
+        assert!(EcdsaSignature::verify(&curve, message, &key_pair.public_key, &signature).unwrap());
+        assert!(!EcdsaSignature::verify(&curve, &message[10..], &key_pair.public_key, &signature).unwrap());
+    }
+}
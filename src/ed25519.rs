@@ -0,0 +1,284 @@
+use num_bigint::BigUint;
+use sha2::{Digest, Sha512};
+use std::sync::OnceLock;
+use crate::finite_field::{Field, FiniteField, SqrtRatio};
+
+// Ed25519 (RFC 8032): the twisted Edwards curve -x^2 + y^2 = 1 + d*x^2*y^2 over p = 2^255 - 19,
+// birationally equivalent to Curve25519; its points do not fit the short Weierstrass
+// EllipticCurve, so the complete Edwards addition law lives here
+pub struct Ed25519KeyPair {
+    pub secret: [u8; 32],
+    pub public: [u8; 32],
+}
+
+// the 32-byte secret is the RFC 8032 private key (the seed), hashed into the signing scalar
+// and the nonce prefix
+pub fn keypair_from_seed(seed: &[u8; 32]) -> Result<Ed25519KeyPair, String> {
+    let (scalar, _) = expand_secret(seed);
+    let public = params().mul_base(&scalar).encode()?;
+    Ok(Ed25519KeyPair { secret: *seed, public })
+}
+
+// deterministic signature bytes(R) || S, R = r*B with r = SHA-512(prefix || M) mod L
+// and S = r + SHA-512(R || A || M) * s mod L
+pub fn sign(secret: &[u8; 32], message: &[u8]) -> Result<[u8; 64], String> {
+    let params = params();
+    let (scalar, prefix) = expand_secret(secret);
+    let public = params.mul_base(&scalar).encode()?;
+
+    let r = hash_to_scalar(&[&prefix, message]);
+    let r_bytes = params.mul_base(&r).encode()?;
+    let k = hash_to_scalar(&[&r_bytes, &public, message]);
+    let s = (r + k * scalar) % &params.l;
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r_bytes);
+    signature[32..].copy_from_slice(&to_32_bytes_le(&s));
+    Ok(signature)
+}
+
+// cofactored verification [8][S]B = [8]R + [8][k]A; encodings that do not decode to curve
+// points and S >= L are rejected as invalid signatures rather than errors
+pub fn verify(public: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> Result<bool, String> {
+    let params = params();
+    let (public_point, r_point) = match (params.decode(public), params.decode(&signature[..32])) {
+        (Some(a), Some(r)) => (a, r),
+        _ => return Ok(false),
+    };
+    let s = BigUint::from_bytes_le(&signature[32..]);
+    if s >= params.l {
+        return Ok(false);
+    }
+
+    let k = hash_to_scalar(&[&signature[..32], public, message]);
+    let eight = BigUint::from(8u32);
+    let left = params.mul(&params.mul_base(&s), &eight);
+    let right = params.mul(&params.add(&r_point, &params.mul(&public_point, &k)), &eight);
+    Ok(params.equal(&left, &right))
+}
+
+// extended coordinates (X : Y : Z : T) for the affine point (X/Z, Y/Z), with T = XY/Z
+#[derive(Clone, Debug)]
+struct EdwardsPoint {
+    x: FiniteField,
+    y: FiniteField,
+    z: FiniteField,
+    t: FiniteField,
+}
+
+impl EdwardsPoint {
+    // 32 bytes: y little-endian, with the parity of x in the top bit
+    fn encode(&self) -> Result<[u8; 32], String> {
+        let z_inv = self.z.inverse()?;
+        let x = self.x.mul(&z_inv)?;
+        let y = self.y.mul(&z_inv)?;
+
+        let mut bytes = to_32_bytes_le(y.get_value());
+        if !x.is_even() {
+            bytes[31] |= 0x80;
+        }
+        Ok(bytes)
+    }
+}
+
+struct Ed25519Params {
+    field: Field,
+    sqrt_ratio: SqrtRatio,
+    // 2d, as used by the addition law
+    d2: FiniteField,
+    d: FiniteField,
+    // order of the base point, 2^252 + 27742317777372353535851937790883648493
+    l: BigUint,
+    base: EdwardsPoint,
+}
+
+fn params() -> &'static Ed25519Params {
+    static PARAMS: OnceLock<Ed25519Params> = OnceLock::new();
+    PARAMS.get_or_init(|| {
+        let p: BigUint = (BigUint::from(1u32) << 255u32) - 19u32;
+        let field = Field::new(p.clone());
+        // d = -121665 / 121666
+        let d = field.element(&p - 121665u32).div(&field.element(BigUint::from(121666u32))).expect("121666 is invertible mod p");
+        let l = (BigUint::from(1u32) << 252) + BigUint::parse_bytes(b"27742317777372353535851937790883648493", 10).expect("Invalid curve constant");
+        let placeholder = EdwardsPoint { x: field.element(BigUint::from(0u32)), y: field.element(BigUint::from(1u32)), z: field.element(BigUint::from(1u32)), t: field.element(BigUint::from(0u32)) };
+
+        // B is the point with y = 4/5 and even x, decoded once the rest of the parameters exist
+        let sqrt_ratio = SqrtRatio::new(&p).expect("p is an odd prime");
+        let mut params = Ed25519Params { d2: field.add(&d, &d), d, field, sqrt_ratio, l, base: placeholder };
+        let mut base_encoding = [0x66u8; 32];
+        base_encoding[0] = 0x58;
+        params.base = params.decode(&base_encoding).expect("Invalid base point encoding");
+        params
+    })
+}
+
+impl Ed25519Params {
+    fn identity(&self) -> EdwardsPoint {
+        EdwardsPoint {
+            x: self.field.element(BigUint::from(0u32)),
+            y: self.field.element(BigUint::from(1u32)),
+            z: self.field.element(BigUint::from(1u32)),
+            t: self.field.element(BigUint::from(0u32)),
+        }
+    }
+
+    // complete addition law (RFC 8032 5.1.4), also valid for doubling and the identity
+    fn add(&self, p: &EdwardsPoint, q: &EdwardsPoint) -> EdwardsPoint {
+        let f = &self.field;
+        let a = f.mul(&f.sub(&p.y, &p.x), &f.sub(&q.y, &q.x));
+        let b = f.mul(&f.add(&p.y, &p.x), &f.add(&q.y, &q.x));
+        let c = f.mul(&f.mul(&p.t, &self.d2), &q.t);
+        let d = f.mul(&p.z, &q.z).double();
+
+        let (e, ff, g, h) = (f.sub(&b, &a), f.sub(&d, &c), f.add(&d, &c), f.add(&b, &a));
+        EdwardsPoint { x: f.mul(&e, &ff), y: f.mul(&g, &h), z: f.mul(&ff, &g), t: f.mul(&e, &h) }
+    }
+
+    // double-and-add - B = k*A, from the most significant bit down
+    fn mul(&self, point: &EdwardsPoint, k: &BigUint) -> EdwardsPoint {
+        let mut res = self.identity();
+        for i in (0..k.bits()).rev() {
+            res = self.add(&res, &res);
+            if k.bit(i) {
+                res = self.add(&res, point);
+            }
+        }
+        res
+    }
+
+    fn mul_base(&self, k: &BigUint) -> EdwardsPoint {
+        self.mul(&self.base, k)
+    }
+
+    // X1/Z1 = X2/Z2 and Y1/Z1 = Y2/Z2, compared without inversions
+    fn equal(&self, p: &EdwardsPoint, q: &EdwardsPoint) -> bool {
+        let f = &self.field;
+        f.mul(&p.x, &q.z) == f.mul(&q.x, &p.z) && f.mul(&p.y, &q.z) == f.mul(&q.y, &p.z)
+    }
+
+    // x^2 = (y^2 - 1) / (d*y^2 + 1), recovered with sqrt_ratio; None for y >= p, a non-square
+    // or x = 0 with the sign bit set (RFC 8032 5.1.3)
+    fn decode(&self, bytes: &[u8]) -> Option<EdwardsPoint> {
+        let mut y_bytes: [u8; 32] = bytes.try_into().ok()?;
+        let sign = y_bytes[31] >> 7 == 1;
+        y_bytes[31] &= 0x7f;
+
+        let y = BigUint::from_bytes_le(&y_bytes);
+        if y >= self.field.p {
+            return None;
+        }
+        let f = &self.field;
+        let y = f.element(y);
+        let one = f.element(BigUint::from(1u32));
+        let y_squared = f.mul(&y, &y);
+        let u = f.sub(&y_squared, &one);
+        let v = f.add(&f.mul(&self.d, &y_squared), &one);
+
+        let (is_square, mut x) = self.sqrt_ratio.sqrt_ratio(&u, &v).ok()?;
+        if !is_square || (x.get_value() == &BigUint::from(0u32) && sign) {
+            return None;
+        }
+        if x.is_even() == sign {
+            x = x.neg();
+        }
+
+        let t = f.mul(&x, &y);
+        Some(EdwardsPoint { x, y, z: one, t })
+    }
+}
+
+// SHA-512(secret) split into the clamped scalar s (low half, bits 0-2 and 255 cleared,
+// bit 254 set) and the nonce prefix (high half)
+fn expand_secret(secret: &[u8; 32]) -> (BigUint, [u8; 32]) {
+    let hash = Sha512::digest(secret);
+    let mut scalar_bytes = [0u8; 32];
+    scalar_bytes.copy_from_slice(&hash[..32]);
+    scalar_bytes[0] &= 0xf8;
+    scalar_bytes[31] &= 0x7f;
+    scalar_bytes[31] |= 0x40;
+
+    let mut prefix = [0u8; 32];
+    prefix.copy_from_slice(&hash[32..]);
+    (BigUint::from_bytes_le(&scalar_bytes), prefix)
+}
+
+// SHA-512 of the concatenated parts as a little-endian integer mod L
+fn hash_to_scalar(parts: &[&[u8]]) -> BigUint {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    BigUint::from_bytes_le(&hasher.finalize()) % &params().l
+}
+
+fn to_32_bytes_le(value: &BigUint) -> [u8; 32] {
+    let bytes = value.to_bytes_le();
+    let mut out = [0u8; 32];
+    out[..bytes.len()].copy_from_slice(&bytes);
+    out
+}
+
+// Test cases from the RFC 8032 7.1 test vectors
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::hex_array;
+
+    fn check_vector(secret: &str, public: &str, message: &[u8], signature: &str) {
+        let secret: [u8; 32] = hex_array(secret);
+        let public: [u8; 32] = hex_array(public);
+        let signature: [u8; 64] = hex_array(signature);
+
+        assert_eq!(keypair_from_seed(&secret).unwrap().public, public);
+        assert_eq!(sign(&secret, message), Ok(signature));
+        assert_eq!(verify(&public, message, &signature), Ok(true));
+    }
+
+    #[test]
+    fn test_rfc8032_vector_1() {
+        check_vector("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                     "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                     b"",
+                     "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b");
+    }
+
+    #[test]
+    fn test_rfc8032_vector_2() {
+        check_vector("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                     "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                     &[0x72],
+                     "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00");
+    }
+
+    #[test]
+    fn test_rfc8032_vector_3() {
+        check_vector("c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+                     "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                     &[0xaf, 0x82],
+                     "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a");
+    }
+
+    #[test]
+    fn test_invalid_signatures_rejected() {
+        let keypair = keypair_from_seed(&[7u8; 32]).unwrap();
+        let signature = sign(&keypair.secret, b"ed25519 message").unwrap();
+        assert_eq!(verify(&keypair.public, b"ed25519 message", &signature), Ok(true));
+        assert_eq!(verify(&keypair.public, b"other message", &signature), Ok(false));
+
+        let mut tampered = signature;
+        tampered[0] ^= 0x01;
+        assert_eq!(verify(&keypair.public, b"ed25519 message", &tampered), Ok(false));
+
+        // S + L encodes the same residue but must be rejected as non-canonical
+        let s_plus_l = BigUint::from_bytes_le(&signature[32..]) + &params().l;
+        let mut malleated = signature;
+        malleated[32..].copy_from_slice(&to_32_bytes_le(&s_plus_l));
+        assert_eq!(verify(&keypair.public, b"ed25519 message", &malleated), Ok(false));
+
+        // y = p does not decode
+        let mut non_canonical_y = [0xffu8; 32];
+        non_canonical_y[0] = 0xed;
+        non_canonical_y[31] = 0x7f;
+        assert_eq!(verify(&non_canonical_y, b"ed25519 message", &signature), Ok(false));
+    }
+}
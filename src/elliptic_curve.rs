@@ -1,96 +1,389 @@
-use num_bigint::{BigUint};
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use rand::RngCore;
+use crate::finite_field::{is_probable_prime, Field, FieldElement};
+use subtle::{Choice, ConditionallySelectable};
+use sha2::{Digest, Sha256};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 pub use crate::finite_field::FiniteField;
 
-#[derive(PartialEq, Clone, Debug)]
-pub enum Point {
-    Coor(FiniteField, FiniteField),
+// points and curves are generic over the field backend, FiniteField (BigUint mod p) by default
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Point<F = FiniteField> {
+    Coor(F, F),
     Identity,
 }
 
+// canonical order for sorting points (e.g. public keys): the identity first, then by x, then by y
+impl<F: Ord> Ord for Point<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Point::Identity, Point::Identity) => Ordering::Equal,
+            (Point::Identity, Point::Coor(..)) => Ordering::Less,
+            (Point::Coor(..), Point::Identity) => Ordering::Greater,
+            (Point::Coor(x1, y1), Point::Coor(x2, y2)) => x1.cmp(x2).then_with(|| y1.cmp(y2)),
+        }
+    }
+}
+
+impl<F: Ord> PartialOrd for Point<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[derive(PartialEq, Clone, Debug)]
-pub struct EllipticCurve {
-    pub a: FiniteField,
-    pub b: FiniteField,
+pub struct EllipticCurve<F = FiniteField> {
+    pub a: F,
+    pub b: F,
     pub p: BigUint,
-    pub g: Point,
+    pub g: Point<F>,
+    pub n: BigUint,
 }
 
-impl EllipticCurve {
-    pub fn add(&self, c: &Point, d: &Point) -> Result<Point, &'static str> {
+// error from add and double: a fixed message, or the self-check on the resulting point failing,
+// which signals a bug (bad slope, mismatched fields, non-prime p) and carries the attempted point
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CurveError {
+    Message(&'static str),
+    NotOnCurve { operation: &'static str, x3: BigUint, y3: BigUint },
+}
+
+impl core::fmt::Display for CurveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CurveError::Message(message) => f.write_str(message),
+            CurveError::NotOnCurve { operation, x3, y3 } => {
+                write!(f, "Resulting point is not on the curve: {} gave x3 = {:#x}, y3 = {:#x}", operation, x3, y3)
+            }
+        }
+    }
+}
+
+impl From<&'static str> for CurveError {
+    fn from(message: &'static str) -> Self {
+        CurveError::Message(message)
+    }
+}
+
+// for the &'static str APIs built on add and double; the attempted point is dropped
+impl From<CurveError> for &'static str {
+    fn from(error: CurveError) -> Self {
+        match error {
+            CurveError::Message(message) => message,
+            CurveError::NotOnCurve { .. } => "Resulting point is not on the curve",
+        }
+    }
+}
+
+impl From<CurveError> for alloc::string::String {
+    fn from(error: CurveError) -> Self {
+        alloc::string::ToString::to_string(&error)
+    }
+}
+
+impl<F> Point<F> {
+    pub fn is_identity(&self) -> bool {
+        matches!(self, Point::Identity)
+    }
+
+    // x-coordinate, None for the point at infinity
+    pub fn x(&self) -> Option<&F> {
+        match self {
+            Point::Coor(x, _) => Some(x),
+            Point::Identity => None,
+        }
+    }
+
+    // y-coordinate, None for the point at infinity
+    pub fn y(&self) -> Option<&F> {
+        match self {
+            Point::Coor(_, y) => Some(y),
+            Point::Identity => None,
+        }
+    }
+}
+
+impl Point {
+    // owned raw coordinate values, None for the point at infinity
+    pub fn coords(&self) -> Option<(BigUint, BigUint)> {
+        match self {
+            Point::Coor(x, y) => Some((x.get_value().clone(), y.get_value().clone())),
+            Point::Identity => None,
+        }
+    }
+
+    pub fn into_coords(self) -> Option<(BigUint, BigUint)> {
+        match self {
+            Point::Coor(x, y) => Some((x.value, y.value)),
+            Point::Identity => None,
+        }
+    }
+
+    // whether y is even (SEC1 prefix 0x02, the BIP-340 convention), None for the point at infinity
+    pub fn y_parity(&self) -> Option<bool> {
+        self.y().map(FiniteField::is_even)
+    }
+
+    // swap a and b when choice is 1, leave them when it is 0, without branching on choice
+    // both points are laid out as equal-length u64 words [identity flag, x, y, p] (the identity as
+    // flag 1 with zero coordinates, borrowing the other point's p) and every word goes through
+    // subtle's conditional_swap; BigUint allocation and the rebuild afterwards are not constant time
+    pub fn conditional_swap(a: &mut Point, b: &mut Point, choice: Choice) {
+        let p = a.x().or(b.x()).map(|x| x.p.clone()).unwrap_or_default();
+        let width = p.iter_u64_digits().len();
+
+        let mut a_words = a.to_words(&p, width);
+        let mut b_words = b.to_words(&p, width);
+        for (a_word, b_word) in a_words.iter_mut().zip(b_words.iter_mut()) {
+            u64::conditional_swap(a_word, b_word, choice);
+        }
+
+        *a = Point::from_words(&a_words, width);
+        *b = Point::from_words(&b_words, width);
+    }
+
+    fn to_words(&self, p: &BigUint, width: usize) -> Vec<u64> {
+        let zero = BigUint::from(0u32);
+        let (flag, x, y) = match self {
+            Point::Identity => (1, &zero, &zero),
+            Point::Coor(x, y) => (0, x.get_value(), y.get_value()),
+        };
+
+        let mut words = vec![flag];
+        for value in [x, y, p] {
+            let mut digits: Vec<u64> = value.iter_u64_digits().collect();
+            digits.resize(width, 0);
+            words.extend(digits);
+        }
+        words
+    }
+
+    fn from_words(words: &[u64], width: usize) -> Point {
+        let to_biguint = |digits: &[u64]| BigUint::new(digits.iter().flat_map(|d| [*d as u32, (*d >> 32) as u32]).collect());
+        if words[0] == 1 {
+            return Point::Identity;
+        }
+        let p = to_biguint(&words[1 + 2 * width..]);
+        Point::Coor(
+            FiniteField::new_reduced(to_biguint(&words[1..1 + width]), p.clone()),
+            FiniteField::new_reduced(to_biguint(&words[1 + width..1 + 2 * width]), p),
+        )
+    }
+}
+
+// fixed-base table [B, 2B, 4B, ..., 2^(bits(n)-1) B] so that k*B needs only additions
+#[derive(Clone, Debug)]
+pub struct PrecomputedBase<F = FiniteField> {
+    pub base: Point<F>,
+    doubles: Vec<Point<F>>,
+}
+
+// comb table for B with teeth d = ceil(bits(n) / w) bits apart: entry j - 1 is the sum of
+// 2^(i*d) B over the bits i set in j, for j in [1, 2^w)
+#[derive(Clone, Debug)]
+pub struct CombTable<F = FiniteField> {
+    pub base: Point<F>,
+    width: usize,
+    spacing: u64,
+    combinations: Vec<Point<F>>,
+}
+
+// Jacobian projective point (X : Y : Z) for the affine point (X/Z^2, Y/Z^3), Z = 0 is the identity
+// add and double need no field inversion here, only the final conversion back to affine does
+#[derive(Clone, Debug)]
+struct JacobianPoint {
+    x: FiniteField,
+    y: FiniteField,
+    z: FiniteField,
+}
+
+// standard curves as (names, p, a, b, Gx, Gy, n) in hex
+type NamedCurve = (&'static [&'static str], &'static str, &'static str, &'static str, &'static str, &'static str, &'static str);
+
+const NAMED_CURVES: [NamedCurve; 3] = [
+    (
+        &["secp256k1"],
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        "0",
+        "7",
+        "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+    ),
+    (
+        &["secp256r1", "p-256", "prime256v1"],
+        "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+        "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC",
+        "5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B",
+        "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+        "4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
+        "FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551",
+    ),
+    (
+        &["secp384r1", "p-384"],
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFF",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFC",
+        "B3312FA7E23EE7E4988E056BE3F82D19181D9C6EFE8141120314088F5013875AC656398D8A2ED19D2A85C8EDD3EC2AEF",
+        "AA87CA22BE8B05378EB1C71EF320AD746E1D3B628BA79B9859F741E082542A385502F25DBF55296C3A545E3872760AB7",
+        "3617DE4A96262C6F5D9E98BF9292DC29F8F41DBD289A147CE9DA3113B5F0B8C00A60B1CE1D7E819D7A431D7C90EA0E5F",
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC7634D81F4372DDF581A0DB248B0A77AECEC196ACCC52973",
+    ),
+];
+
+// the group law and everything built only on it: add/double/neg/sub, the scalar and
+// multi-scalar multiplications, the fixed-base tables and the validity checks
+impl<F: FieldElement> EllipticCurve<F> {
+    pub fn add(&self, c: &Point<F>, d: &Point<F>) -> Result<Point<F>, CurveError> {
         if !self.is_on_curve(c)? {
-            return Err("Point is not on the curve");
+            return Err("Point is not on the curve".into());
         }
         if !self.is_on_curve(d)? {
-            return Err("Point is not on the curve");
+            return Err("Point is not on the curve".into());
         }
+        self.add_unchecked(c, d)
+    }
 
-        match (c, d) {
-            (Point::Identity, _) => Ok(d.clone()),
-            (_, Point::Identity) => Ok(c.clone()),
-            (Point::Coor(x1, y1), Point::Coor(x2, y2)) =>  {
-                if x1 == x2 && y1.add(&y2)? == FiniteField::new(BigUint::from(0u32), self.p.clone()) {
-                    return Ok(Point::Identity);
-                }
-                //  s = (y2 -y1) / (x2 - x1) mod p
-                // x3 = s^2 - x1 - x2 mod p
-                // y3 = -s(x3 - x1) -x1 mod p
-                let slope_num = y2.sub(&y1)?;
-                let slope_den = x2.sub(&x1)?;
-                let s = slope_num.div(&slope_den)?;
-
-                let x3_y3 = self.compute_x3_y3(&x1, &y1, &x2, &s)?;
-                Ok(Point::Coor(x3_y3.0, x3_y3.1))
-            }
-        }
+    // C + D for points already known to be on the curve, as inside the scalar multiplication loops:
+    // sums and doublings of on-curve points stay on the curve, so those loops check their inputs
+    // and their result once instead of every intermediate point twice
+    fn add_unchecked(&self, c: &Point<F>, d: &Point<F>) -> Result<Point<F>, CurveError> {
+        let mut res = c.clone();
+        self.add_unchecked_in_place(&mut res, d)?;
+        Ok(res)
     }
 
-    pub fn double(&self, c: &Point) -> Result<Point, &'static str> {
+    pub fn double(&self, c: &Point<F>) -> Result<Point<F>, CurveError> {
         if !self.is_on_curve(c)? {
-            return Err("Point is not on the curve");
+            return Err("Point is not on the curve".into());
         }
+        self.double_unchecked(c)
+    }
 
-        match c {
-            Point::Identity => Ok(Point::Identity),
-            Point::Coor(x1, y1) => {
-                // s = (3 * x1^2 + a) / (2 * y1) mod p
-                // x3 = s^2 - 2 * x1 mod p
-                // y3 = s(x1 - x3) - y1 mod p
-            let x_squared = x1.mul(x1)?;
-            let three = FiniteField::new(BigUint::from(3u32), self.p.clone());
-            let three_times_x_squared = x_squared.mul(&three)?;
-            let slope_num = three_times_x_squared.add(&self.a)?;
+    // acc += rhs, updating acc's coordinates instead of building a new point
+    pub fn add_in_place(&self, acc: &mut Point<F>, rhs: &Point<F>) -> Result<(), CurveError> {
+        if !self.is_on_curve(acc)? || !self.is_on_curve(rhs)? {
+            return Err("Point is not on the curve".into());
+        }
+        self.add_unchecked_in_place(acc, rhs)
+    }
 
-            let two_y1 = y1.mul(&FiniteField::new(BigUint::from(2u32), self.p.clone()))?;
+    // p = 2p
+    pub fn double_in_place(&self, p: &mut Point<F>) -> Result<(), CurveError> {
+        if !self.is_on_curve(p)? {
+            return Err("Point is not on the curve".into());
+        }
+        self.double_unchecked_in_place(p)
+    }
 
-            let s = slope_num.div(&two_y1)?;
+    // acc += rhs for points already known to be on the curve, see add_unchecked
+    // acc's coordinates are overwritten with the sum rather than replaced by a new point
+    fn add_unchecked_in_place(&self, acc: &mut Point<F>, rhs: &Point<F>) -> Result<(), CurveError> {
+        #[cfg(any(test, feature = "op-counter"))]
+        crate::op_counter::record_addition();
 
-            let x3_y3 = self.compute_x3_y3(x1, y1, x1, &s)?;
-            Ok(Point::Coor(x3_y3.0, x3_y3.1))
+        let (x1, y1, x2, y2) = match (&mut *acc, rhs) {
+            (_, Point::Identity) => return Ok(()),
+            (Point::Identity, _) => {
+                *acc = rhs.clone();
+                return Ok(());
             }
+            (Point::Coor(x1, y1), Point::Coor(x2, y2)) => (x1, y1, x2, y2),
+        };
+        if x1 == x2 && *y1 == y2.neg() {
+            *acc = Point::Identity;
+            return Ok(());
         }
+        // P + P has no chord, the slope is the tangent's
+        if x1 == x2 && y1 == y2 {
+            return self.double_unchecked_in_place(acc);
+        }
+        // neither P + P nor P + (-P), only possible for off-curve inputs; the chord
+        // slope would divide by x2 - x1 = 0
+        if x1 == x2 {
+            return Err("Points share an x-coordinate but are neither equal nor negations".into());
+        }
+        //  s = (y2 -y1) / (x2 - x1) mod p
+        // x3 = s^2 - x1 - x2 mod p
+        let slope_num = y2.sub(y1)?;
+        let slope_den = x2.sub(x1)?;
+        let s = slope_num.div(&slope_den)?;
+
+        let mut x3 = s.mul(&s)?;
+        x3.sub_assign(x1)?;
+        x3.sub_assign(x2)?;
+        self.finish_in_place("add", x1, y1, x3, &s)
     }
-    // x3 = s^2 - x1 -x2 mod p
-    // y3 = s(x1 -x3) -y1 mod p
-    fn compute_x3_y3(&self, x1: &FiniteField, y1: &FiniteField, x2: &FiniteField, s: &FiniteField) -> Result<(FiniteField, FiniteField), &'static str> {
-        let s_squared = s.mul(&s)?;
-        let x1_plus_x2 = x1.add(&x2)?;
-        let x3 = s_squared.sub(&x1_plus_x2)?;
 
-        let x1_minus_x3 = x1.sub(&x3)?;
-        let s_times_x1_minus_x3 = s.mul(&x1_minus_x3)?;
-        let y3 = s_times_x1_minus_x3.sub(&y1)?;
+    // p = 2p for a point already known to be on the curve, see add_unchecked
+    fn double_unchecked_in_place(&self, p: &mut Point<F>) -> Result<(), CurveError> {
+        #[cfg(any(test, feature = "op-counter"))]
+        crate::op_counter::record_doubling();
 
-        if !self.is_on_curve(&Point::Coor(x3.clone(), y3.clone()))? {
-            return Err("Resulting point is not on the curve");
+        let (x1, y1) = match p {
+            Point::Identity => return Ok(()),
+            Point::Coor(x1, y1) => (x1, y1),
+        };
+        // s = (3 * x1^2 + a) / (2 * y1) mod p
+        // x3 = s^2 - 2 * x1 mod p
+        let x_squared = x1.mul(x1)?;
+        let three_times_x_squared = x_squared.mul_small(3)?;
+        let slope_num = three_times_x_squared.add(&self.a)?;
+
+        let two_y1 = y1.double()?;
+
+        let s = slope_num.div(&two_y1)?;
+
+        let mut x3 = s.mul(&s)?;
+        x3.sub_assign(&x1.double()?)?;
+        self.finish_in_place("double", x1, y1, x3, &s)
+    }
+
+    // 2C for a point already known to be on the curve, see add_unchecked
+    fn double_unchecked(&self, c: &Point<F>) -> Result<Point<F>, CurveError> {
+        let mut res = c.clone();
+        self.double_unchecked_in_place(&mut res)?;
+        Ok(res)
+    }
+
+    // -P = (x, -y), the identity is its own negation
+    pub fn neg(&self, c: &Point<F>) -> Result<Point<F>, &'static str> {
+        if !self.is_on_curve(c)? {
+            return Err("Point is not on the curve");
+        }
+
+        match c {
+            Point::Identity => Ok(Point::Identity),
+            Point::Coor(x, y) => Ok(Point::Coor(x.clone(), y.neg())),
         }
+    }
+
+    // C - D = C + (-D)
+    pub fn sub(&self, c: &Point<F>, d: &Point<F>) -> Result<Point<F>, CurveError> {
+        let neg_d = self.neg(d)?;
+        self.add(c, &neg_d)
+    }
+
+    // y3 = s(x1 - x3) - y1 mod p, computed in x1's storage, then (x1, y1) = (x3, y3)
+    fn finish_in_place(&self, operation: &'static str, x1: &mut F, y1: &mut F, x3: F, s: &F) -> Result<(), CurveError> {
+        x1.sub_assign(&x3)?;
+        x1.mul_assign(s)?;
+        x1.sub_assign(y1)?;
+        *y1 = core::mem::replace(x1, x3);
 
-        Ok((x3, y3))
+        // self-check only in debug builds, the formulas are correct by construction, so a failure
+        // is a bug (bad slope, mismatched fields, non-prime p) reported with the attempted point
+        if cfg!(debug_assertions) && !self.is_on_curve(&Point::Coor(x1.clone(), y1.clone()))? {
+            return Err(CurveError::NotOnCurve { operation, x3: x1.to_biguint(), y3: y1.to_biguint() });
+        }
+        Ok(())
     }
     
     // add-double algorithm for scalar multiplication - B = d*A
     // index increasing from LSB to MSB\
 
-    // pub fn scalar_mul(&self, p: &Point, s: BigUint) -> Result<Point, &'static str> {
+    // pub fn scalar_mul(&self, p: &Point<F>, s: BigUint) -> Result<Point<F>, &'static str> {
     //         // Convert s to a vector of bits (LSB to MSB)
     //         let bits = s.to_radix_le(2);
         
@@ -113,64 +406,209 @@ impl EllipticCurve {
     //         Ok(res)
     //     }
 
-    // double-add algorithm for scalar multiplication - B =d*A
-    // index decreasing from MSB to LSB
+    // double-add algorithm for scalar multiplication - B = d*A
+    // index decreasing from MSB to LSB over the bit vector of s, computed once up front
+    // A is checked once on the way in and B once on the way out, the loop itself runs unchecked
+    pub fn double_add(&self, p: &Point<F>, s: &BigUint) -> Result<Point<F>, &'static str> {
+        if !self.is_on_curve(p)? {
+            return Err("Point is not on the curve");
+        }
 
-    // pub fn scalar_mul(&self, p: &Point, s: BigUint) -> Result<Point, &'static str> {
-    //     // Check if the scalar s is zero
-    //     if s == BigUint::from(0u32) {
-    //         return Ok(Point::Identity); // Return the identity point for scalar 0
-    //     }
-    
-    //     let bits = s.to_radix_le(2);
-    //     let mut res = p.clone(); // Start with point P
-    //     let mut i = bits.len() - 1;
-    
-    //     while i > 0 {
-    //         i -= 1;
-    //         res = self.double(&res)?; // Double the point
-    
-    //         if bits[i] == 1 {
-    //             res = self.add(&res, p)?; // Add P if the current bit is 1
-    //         }
-    //     }
-    
-    //     Ok(res)
-    // }
+        let bits = s.to_radix_le(2);
+        let mut res = Point::Identity;
 
-    // Recursively compute the scalar multiplication - B = d*A
-    pub fn scalar_mul(&self, p: &Point, s: BigUint) -> Result<Point, &'static str> {
+        for bit in bits.iter().rev() {
+            self.double_unchecked_in_place(&mut res)?; // Double the point
+
+            if *bit == 1 {
+                self.add_unchecked_in_place(&mut res, p)?; // Add P if the current bit is 1
+            }
+        }
+
+        self.checked_result(res)
+    }
+
+    // scalar multiplication - B = d*A, delegating to the iterative double-add
+    pub fn scalar_mul(&self, p: &Point<F>, s: &BigUint) -> Result<Point<F>, &'static str> {
+        self.double_add(p, s)
+    }
+
+    // signed scalar multiplication - B = d*A for any integer d, with (-d)*A = -(d*A)
+    pub fn scalar_mul_signed(&self, p: &Point<F>, s: &BigInt) -> Result<Point<F>, &'static str> {
+        let product = self.scalar_mul(p, s.magnitude())?;
+        if s.sign() == Sign::Minus {
+            self.neg(&product)
+        } else {
+            Ok(product)
+        }
+    }
+
+    // only the x-coordinate of d*A, which is all ECDH needs as key material
+    // for now the full point is computed; an x-only ladder can replace this without API changes
+    pub fn scalar_mul_x_only(&self, p: &Point<F>, s: &BigUint) -> Result<F, &'static str> {
+        match self.scalar_mul(p, s)? {
+            Point::Coor(x, _) => Ok(x),
+            Point::Identity => Err("Result is the point at infinity"),
+        }
+    }
+
+    // the single on-curve check at the end of an unchecked loop
+    fn checked_result(&self, p: Point<F>) -> Result<Point<F>, &'static str> {
         if !self.is_on_curve(&p)? {
+            return Err("Resulting point is not on the curve");
+        }
+        Ok(p)
+    }
+
+    // windowed NAF scalar multiplication - B = d*A
+    // d is recoded into signed odd digits |di| < 2^(width-1) with at most one nonzero digit in any
+    // width consecutive positions, so runs of 1-bits cost a single add or subtract; the odd
+    // multiples A, 3A, ..., (2^(width-1)-1)A are precomputed once
+    pub fn scalar_mul_wnaf(&self, p: &Point<F>, s: &BigUint, width: usize) -> Result<Point<F>, &'static str> {
+        if !(2..=16).contains(&width) {
+            return Err("wNAF width must be between 2 and 16");
+        }
+        if !self.is_on_curve(p)? {
             return Err("Point is not on the curve");
-        } 
-        else if s == BigUint::from(0u32) { // Check if the scalar s is zero
-            return Ok(Point::Identity);
-        } 
-        else if s.clone() == BigUint::from(1u32) {  // Check if the scalar s is one
-            return Ok(p.clone());
-        } 
-        else if s.clone() % BigUint::from(2u32) == BigUint::from(1u32) {
-            let scalar_mul_result = self.scalar_mul(p, s - BigUint::from(1u32))?;
-            self.add(p, &scalar_mul_result) // addtion when s is odd
-        } 
-        else { // 
-            let double_result = self.double(p)?; // double when s is even
-            self.scalar_mul(&double_result, s / BigUint::from(2u32))
         }
+
+        let twice = self.double_unchecked(p)?;
+        let mut odd_multiples = vec![p.clone()];
+        for i in 1..(1usize << (width - 2)) {
+            let prev = &odd_multiples[i - 1];
+            let next = self.add_unchecked(prev, &twice)?;
+            odd_multiples.push(next);
+        }
+
+        let mut res = Point::Identity;
+        for digit in wnaf_digits(s, width).iter().rev() {
+            res = self.double_unchecked(&res)?;
+            if *digit > 0 {
+                let addend = &odd_multiples[(*digit as usize) / 2];
+                res = self.add_unchecked(&res, addend)?;
+            } else if *digit < 0 {
+                let addend = self.neg(&odd_multiples[(-*digit as usize) / 2])?;
+                res = self.add_unchecked(&res, &addend)?;
+            }
+        }
+
+        self.checked_result(res)
+    }
+
+    // multi-scalar multiplication - B = a1*P1 + a2*P2 + ... + ak*Pk
+    // Shamir's trick: all scalars share one chain of doublings from MSB to LSB,
+    // adding Pi whenever bit i of ai is set
+    pub fn multi_scalar_mul(&self, pairs: &[(BigUint, Point<F>)]) -> Result<Point<F>, &'static str> {
+        for (_, point) in pairs {
+            if !self.is_on_curve(point)? {
+                return Err("Point is not on the curve");
+            }
+        }
+
+        let max_bits = pairs.iter().map(|(s, _)| s.bits()).max().unwrap_or(0);
+        let mut res = Point::Identity;
+
+        for i in (0..max_bits).rev() {
+            self.double_unchecked_in_place(&mut res)?;
+            for (s, point) in pairs {
+                if s.bit(i) {
+                    self.add_unchecked_in_place(&mut res, point)?;
+                }
+            }
+        }
+
+        self.checked_result(res)
+    }
+
+    // Pippenger's bucket method - B = s1*P1 + ... + sk*Pk for many terms
+    // the scalars are cut into window-bit digits; for each window, from the top, every point is
+    // added once into the bucket of its digit, and the buckets are combined as sum(j * bucket_j)
+    // with two running sums, so a window costs about k + 2^(window+1) additions however large k is
+    pub fn pippenger(&self, pairs: &[(BigUint, Point<F>)], window: usize) -> Result<Point<F>, &'static str> {
+        if !(1..=16).contains(&window) {
+            return Err("Pippenger window must be between 1 and 16");
+        }
+        for (_, point) in pairs {
+            if !self.is_on_curve(point)? {
+                return Err("Point is not on the curve");
+            }
+        }
+
+        let max_bits = pairs.iter().map(|(s, _)| s.bits()).max().unwrap_or(0);
+        let windows = max_bits.div_ceil(window as u64);
+        let mut res = Point::Identity;
+
+        for w in (0..windows).rev() {
+            for _ in 0..window {
+                self.double_unchecked_in_place(&mut res)?;
+            }
+
+            let mut buckets = vec![Point::Identity; (1 << window) - 1];
+            for (s, point) in pairs {
+                let digit = (0..window as u64).filter(|i| s.bit(w * window as u64 + i))
+                                              .fold(0usize, |digit, i| digit | (1 << i));
+                if digit != 0 {
+                    self.add_unchecked_in_place(&mut buckets[digit - 1], point)?;
+                }
+            }
+
+            // running holds bucket_j + ... + bucket_top, so summing it for every j weights
+            // bucket_j by j
+            let mut running = Point::Identity;
+            let mut window_sum = Point::Identity;
+            for bucket in buckets.iter().rev() {
+                self.add_unchecked_in_place(&mut running, bucket)?;
+                self.add_unchecked_in_place(&mut window_sum, &running)?;
+            }
+            self.add_unchecked_in_place(&mut res, &window_sum)?;
+        }
+
+        self.checked_result(res)
+    }
+
+    // double-scalar multiplication - B = u1*P + u2*Q, as used by ECDSA verification
+    // Shamir's trick over the joint width max(bits(u1), bits(u2)), with P + Q precomputed once
+    // so every step costs one doubling and at most one addition
+    pub fn scalar_mul_double(&self, u1: &BigUint, p: &Point<F>, u2: &BigUint, q: &Point<F>) -> Result<Point<F>, &'static str> {
+        if !self.is_on_curve(p)? || !self.is_on_curve(q)? {
+            return Err("Point is not on the curve");
+        }
+
+        let sum = self.add_unchecked(p, q)?;
+        let width = u1.bits().max(u2.bits());
+        let mut res = Point::Identity;
+
+        for i in (0..width).rev() {
+            res = self.double_unchecked(&res)?;
+            let addend = match (u1.bit(i), u2.bit(i)) {
+                (true, true) => &sum,
+                (true, false) => p,
+                (false, true) => q,
+                (false, false) => continue,
+            };
+            res = self.add_unchecked(&res, addend)?;
+        }
+
+        self.checked_result(res)
     }
 
     // check wether the point is on the curve or not
     // y^2 = x^3 + ax + b mod p
-    pub fn is_on_curve(&self, c: &Point) -> Result<bool, &'static str> {
+    pub fn is_on_curve(&self, c: &Point<F>) -> Result<bool, &'static str> {
         match c {
-            Point::Identity => Ok(Point::Identity == Point::Identity),
+            Point::Identity => Ok(true),
             Point::Coor(x, y) => {
+                // coordinates from another field (another curve's p) or left unreduced would
+                // otherwise give a meaningless answer rather than an error
+                if !x.in_field(&self.p) || !y.in_field(&self.p) {
+                    return Err("Point coordinates are not reduced elements of the curve's field");
+                }
                 //y^2 
-                let y_squared = y.mul(&y)?;
+                let y_squared = y.mul(y)?;
                 //x^3 
-                let x_cubed = x.mul(&x)?.mul(&x)?;
+                let x_cubed = x.mul(x)?.mul(x)?;
     
-                let ax = self.a.mul(&x)?;
+                let ax = self.a.mul(x)?;
                 // check y^2 = x^3 + ax + b mod p
                 let right_side = x_cubed.add(&ax)?.add(&self.b)?;
     
@@ -178,83 +616,573 @@ impl EllipticCurve {
             }
         }
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    // the affine point (x, y) from user-supplied coordinates, checked to be on the curve
+    // coordinates must already be below p, as in deserialize_point, rather than silently reduced
+    pub fn point(&self, x: BigUint, y: BigUint) -> Result<Point<F>, &'static str> {
+        if x >= self.p || y >= self.p {
+            return Err("Coordinate is not less than p");
+        }
+        let point = Point::Coor(F::from_biguint(x, &self.p), F::from_biguint(y, &self.p));
+        if !self.is_on_curve(&point)? {
+            return Err("Point is not on the curve");
+        }
+        Ok(point)
+    }
 
-    #[test]
-    fn test_add() {
-        //y^2 = x^3 + 2x + 2 mod 17
-        let curve = EllipticCurve {
-            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
-            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
-            p: BigUint::from(17u32),
-            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
-        };
+    // sanity check of the group order: G is an affine curve point, n is prime and n*G = O
+    // for prime-order groups that makes n exactly the order of G; meant for curves built from
+    // external parameters, e.g. right after from_named or a hand-written EllipticCurve
+    pub fn check_order(&self) -> Result<(), &'static str> {
+        if self.g.is_identity() || !self.is_on_curve(&self.g)? {
+            return Err("Generator is not an affine point on the curve");
+        }
+        if !is_probable_prime(&self.n) {
+            return Err("Group order n is not prime");
+        }
+        if !self.scalar_mul(&self.g, &self.n)?.is_identity() {
+            return Err("n*G is not the point at infinity");
+        }
+        Ok(())
+    }
 
-        // (5, 1) + (6, 3) = (10, 6)
-        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let p2 = Point::Coor(FiniteField::new(BigUint::from(6u32), curve.p.clone()), FiniteField::new(BigUint::from(3u32), curve.p.clone()));
-        let p3 = Point::Coor(FiniteField::new(BigUint::from(10u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
-        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+    // same domain parameters p, n, a, b and G, the moduli first since they are the cheapest to
+    // tell apart; the derived PartialEq compares the same fields, coordinates as residues mod p
+    pub fn same_params(&self, other: &EllipticCurve<F>) -> bool {
+        self.p == other.p && self.n == other.n && self.a == other.a && self.b == other.b && self.g == other.g
+    }
 
-        // (5, 1) + (3, 1) = (9, 16)
-        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let p2 = Point::Coor(FiniteField::new(BigUint::from(3u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let p3 = Point::Coor(FiniteField::new(BigUint::from(9u32), curve.p.clone()), FiniteField::new(BigUint::from(16u32), curve.p.clone()));
-        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+    // precompute the doublings of a base point of order n (the generator or a public key)
+    pub fn precompute_base(&self, base: &Point<F>) -> Result<PrecomputedBase<F>, &'static str> {
+        if !self.is_on_curve(base)? {
+            return Err("Point is not on the curve");
+        }
 
-        // (5, 1) + (9, 16) = (16, 13)
-        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let p2 = Point::Coor(FiniteField::new(BigUint::from(9u32), curve.p.clone()), FiniteField::new(BigUint::from(16u32), curve.p.clone()));
-        let p3 = Point::Coor(FiniteField::new(BigUint::from(16u32), curve.p.clone()), FiniteField::new(BigUint::from(13u32), curve.p.clone()));
-        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+        let mut doubles = Vec::with_capacity(self.n.bits() as usize);
+        let mut current = base.clone();
+        for _ in 0..self.n.bits() {
+            let next = self.double(&current)?;
+            doubles.push(current);
+            current = next;
+        }
 
-        // (5, 1) + (16, 13) = (0, 6)
-        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let p2 = Point::Coor(FiniteField::new(BigUint::from(16u32), curve.p.clone()), FiniteField::new(BigUint::from(13u32), curve.p.clone()));
-        let p3 = Point::Coor(FiniteField::new(BigUint::from(0u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
-        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+        Ok(PrecomputedBase { base: base.clone(), doubles })
+    }
 
-        // (5, 1) + (0, 6) = (13, 7)
-        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let p2 = Point::Coor(FiniteField::new(BigUint::from(0u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
-        let p3 = Point::Coor(FiniteField::new(BigUint::from(13u32), curve.p.clone()), FiniteField::new(BigUint::from(7u32), curve.p.clone()));
-        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+    // fixed-base scalar multiplication - B = d*A using a precomputed table for A
+    // d is reduced mod n first, since the table only covers bits(n) bits
+    pub fn mul_precomputed(&self, table: &PrecomputedBase<F>, s: &BigUint) -> Result<Point<F>, &'static str> {
+        let s = s % &self.n;
+        let mut res = Point::Identity;
 
-        // (5, 1) + (13, 7) = (7, 6)
-        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let p2 = Point::Coor(FiniteField::new(BigUint::from(13u32), curve.p.clone()), FiniteField::new(BigUint::from(7u32), curve.p.clone()));
-        let p3 = Point::Coor(FiniteField::new(BigUint::from(7u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
-        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+        for (i, point) in table.doubles.iter().enumerate() {
+            if s.bit(i as u64) {
+                res = self.add(&res, point)?;
+            }
+        }
 
+        Ok(res)
     }
 
-    #[test]
-    fn test_double() {
-        //y^ 2 = x^3 + 2x + 2 mod 17
-        let curve = EllipticCurve {
-            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
-            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
-            p: BigUint::from(17u32),
-            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
-        };
+    // comb precomputation for a fixed base: the 2^w - 1 combinations of the w points
+    // B, 2^d B, 2^(2d) B, ..., 2^((w-1)d) B with d = ceil(bits(n) / w)
+    pub fn comb_precompute(&self, base: &Point<F>, w: usize) -> Result<CombTable<F>, &'static str> {
+        if !(1..=8).contains(&w) {
+            return Err("Comb width must be between 1 and 8");
+        }
+        if !self.is_on_curve(base)? {
+            return Err("Point is not on the curve");
+        }
 
-        // 2(5, 1) = (6, 3) -> d = 2
-        let point = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let double_point = Point::Coor(FiniteField::new(BigUint::from(6u32), curve.p.clone()), FiniteField::new(BigUint::from(3u32), curve.p.clone()));
-        let calculated_double_point = curve.double(&point);
-        assert_eq!(calculated_double_point, Ok(double_point));
+        let spacing = self.n.bits().div_ceil(w as u64).max(1);
+        let mut teeth = vec![base.clone()];
+        for i in 1..w {
+            let mut tooth = teeth[i - 1].clone();
+            for _ in 0..spacing {
+                self.double_unchecked_in_place(&mut tooth)?;
+            }
+            teeth.push(tooth);
+        }
 
-        // 2(6, 3) = (3, 1) -> d = 4
-        let point = Point::Coor(FiniteField::new(BigUint::from(6u32), curve.p.clone()), FiniteField::new(BigUint::from(3u32), curve.p.clone()));
-        let double_point = Point::Coor(FiniteField::new(BigUint::from(3u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        let calculated_double_point = curve.double(&point);
-        assert_eq!(calculated_double_point, Ok(double_point));
+        // each combination is a smaller one plus the tooth of its highest bit
+        let mut combinations: Vec<Point<F>> = Vec::with_capacity((1 << w) - 1);
+        for j in 1usize..(1 << w) {
+            let high = (usize::BITS - 1 - j.leading_zeros()) as usize;
+            let rest = j ^ (1 << high);
+            let combination = if rest == 0 {
+                teeth[high].clone()
+            } else {
+                self.add_unchecked(&combinations[rest - 1], &teeth[high])?
+            };
+            combinations.push(combination);
+        }
 
-        // 2(3, 1) = (13, 7) -> d = 8
+        Ok(CombTable { base: base.clone(), width: w, spacing, combinations })
+    }
+
+    // fixed-base comb multiplication - B = d*A, one doubling per column and at most one
+    // table addition, so about bits(n)/w doublings in total; d is reduced mod n first
+    pub fn comb_mul(&self, table: &CombTable<F>, s: &BigUint) -> Result<Point<F>, &'static str> {
+        let s = s % &self.n;
+        let mut res = Point::Identity;
+
+        for column in (0..table.spacing).rev() {
+            self.double_unchecked_in_place(&mut res)?;
+
+            let index = (0..table.width).filter(|i| s.bit(*i as u64 * table.spacing + column))
+                                        .fold(0usize, |index, i| index | (1 << i));
+            if index != 0 {
+                self.add_unchecked_in_place(&mut res, &table.combinations[index - 1])?;
+            }
+        }
+
+        self.checked_result(res)
+    }
+
+    // a usable public key: not the identity, on the curve and in the subgroup of order n
+    // the subgroup check is a full n*P, so it is skipped when the Hasse bound
+    // #E <= p + 1 + 2*sqrt(p) < 2n already forces the cofactor to be 1
+    pub fn validate_public_key(&self, p: &Point<F>) -> Result<(), &'static str> {
+        if p.is_identity() {
+            return Err("Public key is the point at infinity");
+        }
+        if !self.is_on_curve(p)? {
+            return Err("Public key is not on the curve");
+        }
+
+        let hasse_bound = &self.p + 1u32 + (self.p.sqrt() + 1u32) * 2u32;
+        if hasse_bound >= &self.n * 2u32 && !self.scalar_mul(p, &self.n)?.is_identity() {
+            return Err("Public key is not in the subgroup of order n");
+        }
+
+        Ok(())
+    }
+}
+
+// FiniteField only: named curves and SEC1 encoding read and write BigUint coordinates,
+// lift_x, hash_to_point and random_point need sqrt, the ladder swaps the BigUint words of
+// its points, and the Jacobian path, point counting and j-invariant use Field or Fp specifics
+impl EllipticCurve {
+    // look up a standard curve by name (case-insensitive): "secp256k1",
+    // "secp256r1" / "P-256" / "prime256v1" or "secp384r1" / "P-384"
+    pub fn from_named(name: &str) -> Result<EllipticCurve, &'static str> {
+        let name = name.to_ascii_lowercase();
+        let (_, p, a, b, gx, gy, n) = NAMED_CURVES.iter()
+                                                  .find(|(names, ..)| names.contains(&name.as_str()))
+                                                  .ok_or("Unknown curve name")?;
+
+        let hex = |value: &str| BigUint::parse_bytes(value.as_bytes(), 16).ok_or("Invalid curve constant");
+        let p = hex(p)?;
+        Ok(EllipticCurve {
+            a: FiniteField::new(hex(a)?, p.clone()),
+            b: FiniteField::new(hex(b)?, p.clone()),
+            g: Point::Coor(FiniteField::new(hex(gx)?, p.clone()), FiniteField::new(hex(gy)?, p.clone())),
+            n: hex(n)?,
+            p,
+        })
+    }
+
+    // k*G for the curve's own generator, using the cached generator table on secp256k1
+    pub fn mul_generator(&self, s: &BigUint) -> Result<Point, &'static str> {
+        crate::secp256k1::mul_generator(self, s)
+    }
+
+    // Montgomery ladder - B = d*A with one addition and one doubling per bit whatever the bits are
+    // R1 - R0 = A holds throughout, and the loop always covers max(bits(n), bits(d)) bits,
+    // so the sequence of point operations does not depend on the scalar
+    pub fn scalar_mul_ladder(&self, p: &Point, s: &BigUint) -> Result<Point, &'static str> {
+        if !self.is_on_curve(p)? {
+            return Err("Point is not on the curve");
+        }
+
+        let mut r0 = Point::Identity;
+        let mut r1 = p.clone();
+        for i in (0..self.n.bits().max(s.bits())).rev() {
+            // bit 1: R0 = R0 + R1, R1 = 2*R1; bit 0: R1 = R0 + R1, R0 = 2*R0
+            let bit = Choice::from(s.bit(i) as u8);
+            Point::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = self.add_unchecked(&r0, &r1)?;
+            r0 = self.double_unchecked(&r0)?;
+            Point::conditional_swap(&mut r0, &mut r1, bit);
+        }
+
+        self.checked_result(r0)
+    }
+
+    // scalar multiplication - B = d*A in Jacobian coordinates, with a single inversion at the end
+    pub fn scalar_mul_fast(&self, p: &Point, s: &BigUint) -> Result<Point, &'static str> {
+        if !self.is_on_curve(p)? {
+            return Err("Point is not on the curve");
+        }
+
+        let field = Field::new(self.p.clone());
+        let base = self.to_jacobian(&field, p);
+        let mut res = self.to_jacobian(&field, &Point::Identity);
+
+        for i in (0..s.bits()).rev() {
+            res = self.jacobian_double(&field, &res);
+            if s.bit(i) {
+                res = self.jacobian_add(&field, &res, &base);
+            }
+        }
+
+        self.to_affine(&field, &res)
+    }
+
+    fn to_jacobian(&self, field: &Field, p: &Point) -> JacobianPoint {
+        match p {
+            Point::Identity => JacobianPoint { x: field.element(BigUint::from(1u32)), y: field.element(BigUint::from(1u32)), z: field.element(BigUint::from(0u32)) },
+            Point::Coor(x, y) => JacobianPoint { x: x.clone(), y: y.clone(), z: field.element(BigUint::from(1u32)) },
+        }
+    }
+
+    // x = X / Z^2, y = Y / Z^3
+    fn to_affine(&self, field: &Field, p: &JacobianPoint) -> Result<Point, &'static str> {
+        if p.z.get_value() == &BigUint::from(0u32) {
+            return Ok(Point::Identity);
+        }
+
+        let z_inv = p.z.inverse()?;
+        let z_inv_squared = field.mul(&z_inv, &z_inv);
+        let x = field.mul(&p.x, &z_inv_squared);
+        let y = field.mul(&p.y, &field.mul(&z_inv_squared, &z_inv));
+        Ok(Point::Coor(x, y))
+    }
+
+    // S = 4XY^2, M = 3X^2 + aZ^4
+    // X' = M^2 - 2S, Y' = M(S - X') - 8Y^4, Z' = 2YZ
+    fn jacobian_double(&self, field: &Field, p: &JacobianPoint) -> JacobianPoint {
+        if p.z.get_value() == &BigUint::from(0u32) || p.y.get_value() == &BigUint::from(0u32) {
+            return self.to_jacobian(field, &Point::Identity);
+        }
+
+        let y_squared = field.mul(&p.y, &p.y);
+        let z_squared = field.mul(&p.z, &p.z);
+        let s = field.mul(&field.element(BigUint::from(4u32)), &field.mul(&p.x, &y_squared));
+        let m = field.add(
+            &field.mul(&field.element(BigUint::from(3u32)), &field.mul(&p.x, &p.x)),
+            &field.mul(&self.a, &field.mul(&z_squared, &z_squared)),
+        );
+
+        let x = field.sub(&field.mul(&m, &m), &field.add(&s, &s));
+        let eight_y_fourth = field.mul(&field.element(BigUint::from(8u32)), &field.mul(&y_squared, &y_squared));
+        let y = field.sub(&field.mul(&m, &field.sub(&s, &x)), &eight_y_fourth);
+        let z = field.mul(&field.add(&p.y, &p.y), &p.z);
+        JacobianPoint { x, y, z }
+    }
+
+    // U1 = X1 Z2^2, U2 = X2 Z1^2, S1 = Y1 Z2^3, S2 = Y2 Z1^3, H = U2 - U1, R = S2 - S1
+    // X3 = R^2 - H^3 - 2 U1 H^2, Y3 = R(U1 H^2 - X3) - S1 H^3, Z3 = H Z1 Z2
+    fn jacobian_add(&self, field: &Field, p: &JacobianPoint, q: &JacobianPoint) -> JacobianPoint {
+        let zero = BigUint::from(0u32);
+        if p.z.get_value() == &zero {
+            return q.clone();
+        }
+        if q.z.get_value() == &zero {
+            return p.clone();
+        }
+
+        let z1_squared = field.mul(&p.z, &p.z);
+        let z2_squared = field.mul(&q.z, &q.z);
+        let u1 = field.mul(&p.x, &z2_squared);
+        let u2 = field.mul(&q.x, &z1_squared);
+        let s1 = field.mul(&p.y, &field.mul(&z2_squared, &q.z));
+        let s2 = field.mul(&q.y, &field.mul(&z1_squared, &p.z));
+
+        if u1 == u2 {
+            // same x: either P + P or P + (-P)
+            return if s1 == s2 { self.jacobian_double(field, p) } else { self.to_jacobian(field, &Point::Identity) };
+        }
+
+        let h = field.sub(&u2, &u1);
+        let r = field.sub(&s2, &s1);
+        let h_squared = field.mul(&h, &h);
+        let h_cubed = field.mul(&h_squared, &h);
+        let u1_h_squared = field.mul(&u1, &h_squared);
+
+        let x = field.sub(&field.sub(&field.mul(&r, &r), &h_cubed), &field.add(&u1_h_squared, &u1_h_squared));
+        let y = field.sub(&field.mul(&r, &field.sub(&u1_h_squared, &x)), &field.mul(&s1, &h_cubed));
+        let z = field.mul(&h, &field.mul(&p.z, &q.z));
+        JacobianPoint { x, y, z }
+    }
+
+    // #E by brute force: for each x in [0, p), y^2 = x^3 + ax + b has 0, 1 or 2 solutions
+    // depending on whether the right side is a non-residue, zero or a non-zero residue,
+    // plus one for the identity. O(p) modpows, so only usable for small educational curves
+    pub fn count_points(&self) -> Result<BigUint, &'static str> {
+        if self.p.bits() > 24 {
+            return Err("Counting points by brute force needs p < 2^24");
+        }
+
+        let mut count = BigUint::from(1u32);
+
+        let mut x_value = BigUint::from(0u32);
+        while x_value < self.p {
+            let x = FiniteField::new_reduced(x_value.clone(), self.p.clone());
+            let right_side = x.mul(&x)?.mul(&x)?.add(&self.a.mul(&x)?)?.add(&self.b)?;
+            // 1 + (rhs/p) solutions for y
+            count += (1 + right_side.legendre_symbol()) as u32;
+            x_value += 1u32;
+        }
+
+        Ok(count)
+    }
+
+    // order of the quadratic twist, 2(p + 1) - #E: the traces of E and its twist are t and -t
+    // built on count_points, so limited to the same small curves
+    pub fn twist_order(&self) -> Result<BigUint, &'static str> {
+        Ok((&self.p + 1u32) * 2u32 - self.count_points()?)
+    }
+
+    // whether the twist order has a prime factor of at least min_bits bits, so an invalid-curve
+    // attack landing on the twist only sees a hard discrete log
+    pub fn is_twist_secure(&self, min_bits: u64) -> Result<bool, &'static str> {
+        let mut remaining = self.twist_order()?;
+        let mut largest_factor = BigUint::from(1u32);
+
+        let mut divisor = BigUint::from(2u32);
+        while &divisor * &divisor <= remaining {
+            while &remaining % &divisor == BigUint::from(0u32) {
+                remaining /= &divisor;
+                largest_factor = divisor.clone();
+            }
+            divisor += 1u32;
+        }
+        if remaining > BigUint::from(1u32) {
+            largest_factor = largest_factor.max(remaining);
+        }
+
+        Ok(largest_factor.bits() >= min_bits)
+    }
+
+    // #E = p + 1 - t from the trace of Frobenius t, as listed by curve references
+    // t must satisfy the Hasse bound t^2 <= 4p
+    pub fn order_from_trace(&self, t: &BigInt) -> Result<BigUint, &'static str> {
+        if t.magnitude().pow(2) > &self.p * 4u32 {
+            return Err("Trace exceeds the Hasse bound 2*sqrt(p)");
+        }
+        (BigInt::from(self.p.clone()) + 1u32 - t).to_biguint().ok_or("Trace exceeds the Hasse bound 2*sqrt(p)")
+    }
+
+    // the two points with x-coordinate x, as (even y, odd y); they are each other's negation
+    // (and the same point when y = 0). Errors if x^3 + ax + b is not a square mod p
+    pub fn lift_x(&self, x: &FiniteField) -> Result<(Point, Point), &'static str> {
+        if x.p != self.p {
+            return Err("x must be an element of the curve's field");
+        }
+        let right_side = x.mul(x)?.mul(x)?.add(&self.a.mul(x)?)?.add(&self.b)?;
+        let y = right_side.sqrt().ok_or("x is not the x-coordinate of a curve point")?;
+        let minus_y = y.neg();
+
+        let (even, odd) = if y.is_even() { (y, minus_y) } else { (minus_y, y) };
+        Ok((Point::Coor(x.clone(), even), Point::Coor(x.clone(), odd)))
+    }
+
+    // hash arbitrary bytes to a curve point by try-and-increment
+    // x = SHA-256(data || counter) mod p, incrementing the 4-byte big-endian counter until
+    // x^3 + ax + b has a square root; the even y is picked so the mapping is deterministic
+    pub fn hash_to_point(&self, data: &[u8]) -> Result<Point, &'static str> {
+        for counter in 0..=u32::MAX {
+            let mut hasher = Sha256::new();
+            hasher.update(data);
+            hasher.update(counter.to_be_bytes());
+            let x = FiniteField::new(BigUint::from_bytes_be(&hasher.finalize()), self.p.clone());
+
+            if let Ok((even, _)) = self.lift_x(&x) {
+                return Ok(even);
+            }
+        }
+
+        Err("No valid point found for the input")
+    }
+
+    // random affine point: a uniform x until x^3 + ax + b is a square, then a random choice of
+    // the two y; about half of all x lift, so 128 attempts fail only with probability ~2^-128
+    // never the identity, and a point with y = 0 is about twice as likely as any other point
+    pub fn random_point<R: RngCore>(&self, rng: &mut R) -> Result<Point, &'static str> {
+        for _ in 0..128 {
+            let x = FiniteField::new(rng.gen_biguint_below(&self.p), self.p.clone());
+            if let Ok((even, odd)) = self.lift_x(&x) {
+                return Ok(if rng.next_u32() & 1 == 0 { even } else { odd });
+            }
+        }
+
+        Err("No curve point found for the random x-coordinates")
+    }
+
+    // SEC1 point encoding: 0x04 || x || y, or 0x02/0x03 || x when compressed (0x03 for odd y)
+    // coordinates are left-padded to the byte length of p
+    pub fn serialize_point(&self, point: &Point, compressed: bool) -> Result<Vec<u8>, &'static str> {
+        if !self.is_on_curve(point)? {
+            return Err("Point is not on the curve");
+        }
+
+        match point {
+            Point::Identity => Err("Cannot serialize the point at infinity"),
+            Point::Coor(x, y) => {
+                let mut bytes = Vec::new();
+                if compressed {
+                    bytes.push(if y.is_even() { 0x02 } else { 0x03 });
+                    bytes.extend(x.to_bytes_be());
+                } else {
+                    bytes.push(0x04);
+                    bytes.extend(x.to_bytes_be());
+                    bytes.extend(y.to_bytes_be());
+                }
+                Ok(bytes)
+            }
+        }
+    }
+
+    // parse a SEC1 compressed or uncompressed point and check it is on the curve
+    pub fn deserialize_point(&self, bytes: &[u8]) -> Result<Point, &'static str> {
+        let size = self.p.bits().div_ceil(8) as usize;
+
+        let point = match bytes.first() {
+            Some(0x04) if bytes.len() == 1 + 2 * size => {
+                let x = BigUint::from_bytes_be(&bytes[1..1 + size]);
+                let y = BigUint::from_bytes_be(&bytes[1 + size..]);
+                if x >= self.p || y >= self.p {
+                    return Err("Coordinate is not less than p");
+                }
+                Point::Coor(FiniteField::new(x, self.p.clone()), FiniteField::new(y, self.p.clone()))
+            }
+            Some(prefix @ (0x02 | 0x03)) if bytes.len() == 1 + size => {
+                let x = BigUint::from_bytes_be(&bytes[1..]);
+                if x >= self.p {
+                    return Err("Coordinate is not less than p");
+                }
+                let (even, odd) = self.lift_x(&FiniteField::new(x, self.p.clone()))?;
+                if *prefix == 0x02 { even } else { odd }
+            }
+            _ => return Err("Invalid SEC1 point encoding"),
+        };
+
+        if !self.is_on_curve(&point)? {
+            return Err("Point is not on the curve");
+        }
+        Ok(point)
+    }
+
+    // j-invariant of the curve, equal for isomorphic curves over Fp
+    // j = 1728 * 4a^3 / (4a^3 + 27b^2) mod p
+    pub fn j_invariant(&self) -> Result<FiniteField, &'static str> {
+        let four_a_cubed = self.a.mul(&self.a)?.mul(&self.a)?.mul(&FiniteField::new(BigUint::from(4u32), self.p.clone()))?;
+        let twenty_seven_b_squared = self.b.mul(&self.b)?.mul(&FiniteField::new(BigUint::from(27u32), self.p.clone()))?;
+        let denominator = four_a_cubed.add(&twenty_seven_b_squared)?;
+        if denominator.get_value() == &BigUint::from(0u32) {
+            return Err("Curve is singular (4a^3 + 27b^2 = 0)");
+        }
+
+        let numerator = four_a_cubed.mul(&FiniteField::new(BigUint::from(1728u32), self.p.clone()))?;
+        numerator.div(&denominator)
+    }
+}
+
+// width-w NAF of s, least significant digit first
+fn wnaf_digits(s: &BigUint, width: usize) -> Vec<i64> {
+    let window = 1i64 << width;
+    let mut k = s.clone();
+    let mut digits = Vec::with_capacity(s.bits() as usize + 1);
+
+    while k.bits() > 0 {
+        let mut digit = 0i64;
+        if k.bit(0) {
+            digit = (k.iter_u64_digits().next().unwrap_or(0) & (window as u64 - 1)) as i64;
+            if digit >= window / 2 {
+                digit -= window;
+            }
+            if digit > 0 {
+                k -= BigUint::from(digit as u64);
+            } else {
+                k += BigUint::from((-digit) as u64);
+            }
+        }
+        digits.push(digit);
+        k >>= 1;
+    }
+
+    digits
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_bigint::RandBigInt;
+
+    #[test]
+    fn test_add() {
+        //y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+
+        // (5, 1) + (6, 3) = (10, 6)
+        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let p2 = Point::Coor(FiniteField::new(BigUint::from(6u32), curve.p.clone()), FiniteField::new(BigUint::from(3u32), curve.p.clone()));
+        let p3 = Point::Coor(FiniteField::new(BigUint::from(10u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
+        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+
+        // (5, 1) + (3, 1) = (9, 16)
+        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let p2 = Point::Coor(FiniteField::new(BigUint::from(3u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let p3 = Point::Coor(FiniteField::new(BigUint::from(9u32), curve.p.clone()), FiniteField::new(BigUint::from(16u32), curve.p.clone()));
+        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+
+        // (5, 1) + (9, 16) = (16, 13)
+        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let p2 = Point::Coor(FiniteField::new(BigUint::from(9u32), curve.p.clone()), FiniteField::new(BigUint::from(16u32), curve.p.clone()));
+        let p3 = Point::Coor(FiniteField::new(BigUint::from(16u32), curve.p.clone()), FiniteField::new(BigUint::from(13u32), curve.p.clone()));
+        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+
+        // (5, 1) + (16, 13) = (0, 6)
+        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let p2 = Point::Coor(FiniteField::new(BigUint::from(16u32), curve.p.clone()), FiniteField::new(BigUint::from(13u32), curve.p.clone()));
+        let p3 = Point::Coor(FiniteField::new(BigUint::from(0u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
+        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+
+        // (5, 1) + (0, 6) = (13, 7)
+        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let p2 = Point::Coor(FiniteField::new(BigUint::from(0u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
+        let p3 = Point::Coor(FiniteField::new(BigUint::from(13u32), curve.p.clone()), FiniteField::new(BigUint::from(7u32), curve.p.clone()));
+        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+
+        // (5, 1) + (13, 7) = (7, 6)
+        let p1 = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let p2 = Point::Coor(FiniteField::new(BigUint::from(13u32), curve.p.clone()), FiniteField::new(BigUint::from(7u32), curve.p.clone()));
+        let p3 = Point::Coor(FiniteField::new(BigUint::from(7u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
+        assert_eq!(curve.add(&p1, &p2), Ok(p3));
+
+    }
+
+    #[test]
+    fn test_double() {
+        //y^ 2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+
+        // 2(5, 1) = (6, 3) -> d = 2
+        let point = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let double_point = Point::Coor(FiniteField::new(BigUint::from(6u32), curve.p.clone()), FiniteField::new(BigUint::from(3u32), curve.p.clone()));
+        let calculated_double_point = curve.double(&point);
+        assert_eq!(calculated_double_point, Ok(double_point));
+
+        // 2(6, 3) = (3, 1) -> d = 4
+        let point = Point::Coor(FiniteField::new(BigUint::from(6u32), curve.p.clone()), FiniteField::new(BigUint::from(3u32), curve.p.clone()));
+        let double_point = Point::Coor(FiniteField::new(BigUint::from(3u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        let calculated_double_point = curve.double(&point);
+        assert_eq!(calculated_double_point, Ok(double_point));
+
+        // 2(3, 1) = (13, 7) -> d = 8
         let point = Point::Coor(FiniteField::new(BigUint::from(3u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
         let double_point = Point::Coor(FiniteField::new(BigUint::from(13u32), curve.p.clone()), FiniteField::new(BigUint::from(7u32), curve.p.clone()));
         let calculated_double_point = curve.double(&point);
@@ -271,52 +1199,53 @@ mod test {
             b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
             p: BigUint::from(17u32),
             g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
         };
         let point = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
 
         // 2(5, 1) = (6, 3)
         let second_point = Point::Coor(FiniteField::new(BigUint::from(6u32), curve.p.clone()), FiniteField::new(BigUint::from(3u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(2u32)), Ok(second_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(2u32)), Ok(second_point));
 
         // 3(5, 1) = (10, 6)
         let second_point = Point::Coor(FiniteField::new(BigUint::from(10u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(3u32)), Ok(second_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(3u32)), Ok(second_point));
 
         // 4(5, 1) = (3, 1)
         let second_point = Point::Coor(FiniteField::new(BigUint::from(3u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(4u32)), Ok(second_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(4u32)), Ok(second_point));
 
         // 5(5, 1) = (9, 16)
         let second_point = Point::Coor(FiniteField::new(BigUint::from(9u32), curve.p.clone()), FiniteField::new(BigUint::from(16u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(5u32)), Ok(second_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(5u32)), Ok(second_point));
 
         // 6(5, 1) = (16, 13) 
         let second_point = Point::Coor(FiniteField::new(BigUint::from(16u32), curve.p.clone()), FiniteField::new(BigUint::from(13u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(6u32)), Ok(second_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(6u32)), Ok(second_point));
 
         // 7(5, 1) = (0, 6)
         let second_point = Point::Coor(FiniteField::new(BigUint::from(0u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(7u32)), Ok(second_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(7u32)), Ok(second_point));
 
         // 8(5, 1) = (13, 7) 
         let second_point = Point::Coor(FiniteField::new(BigUint::from(13u32), curve.p.clone()), FiniteField::new(BigUint::from(7u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(8u32)), Ok(second_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(8u32)), Ok(second_point));
 
         // 9(5, 1) = (7, 6) 
         let second_point = Point::Coor(FiniteField::new(BigUint::from(7u32), curve.p.clone()), FiniteField::new(BigUint::from(6u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(9u32)), Ok(second_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(9u32)), Ok(second_point));
 
         // 10(5, 1) = (7, 11)
         let tenth_point = Point::Coor(FiniteField::new(BigUint::from(7u32), curve.p.clone()), FiniteField::new(BigUint::from(11u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(10u32)), Ok(tenth_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(10u32)), Ok(tenth_point));
 
         // 18(5, 1) = (5, 16)
         let eighteenth_point = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(16u32), curve.p.clone()));
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(18u32)), Ok(eighteenth_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(18u32)), Ok(eighteenth_point));
 
         //19(5, 1) = Point::Idnetity
         let nineteenth_point = Point::Identity;
-        assert_eq!(curve.scalar_mul(&point, BigUint::from(19u32)), Ok(nineteenth_point));
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(19u32)), Ok(nineteenth_point));
     }
 
     #[test]
@@ -327,11 +1256,12 @@ mod test {
             b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
             p: BigUint::from(17u32),
             g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
         };
 
         let point = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
 
-        let calculated_scalar_mul_point = curve.scalar_mul(&point, BigUint::from(0u32));
+        let calculated_scalar_mul_point = curve.scalar_mul(&point, &BigUint::from(0u32));
 
         assert_eq!(calculated_scalar_mul_point, Ok(Point::Identity));
     }
@@ -344,6 +1274,7 @@ mod test {
             b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
             p: BigUint::from(17u32),
             g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
         };
 
         let on_curve_point = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
@@ -354,4 +1285,994 @@ mod test {
 
         assert!(!curve.is_on_curve(&off_curve_point).unwrap(), "Point is not on the curve");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_is_on_curve_rejects_foreign_coordinates() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let (gx, gy) = curve.g.coords().unwrap();
+
+        // G's coordinates as elements of the P-256 field
+        let p256 = EllipticCurve::from_named("P-256").unwrap().p;
+        let foreign = Point::Coor(FiniteField::new(gx.clone(), p256.clone()), FiniteField::new(gy.clone(), p256));
+        assert_eq!(curve.is_on_curve(&foreign), Err("Point coordinates are not reduced elements of the curve's field"));
+
+        // only y from the other field
+        let mixed = Point::Coor(FiniteField::new(gx.clone(), curve.p.clone()), FiniteField::new(gy.clone(), BigUint::from(17u32)));
+        assert!(curve.is_on_curve(&mixed).is_err());
+
+        // x + p is the same residue but not a reduced element
+        let unreduced = Point::Coor(FiniteField { value: &gx + &curve.p, p: curve.p.clone() }, FiniteField::new(gy, curve.p.clone()));
+        assert!(curve.is_on_curve(&unreduced).is_err());
+        assert_eq!(curve.is_on_curve(&curve.g), Ok(true));
+    }
+
+    #[test]
+    fn test_j_invariant() {
+        // secp256k1 has a = 0, so j = 0
+        let secp256k1 = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        assert_eq!(secp256k1.j_invariant(), Ok(FiniteField::new(BigUint::from(0u32), secp256k1.p.clone())));
+
+        // y^2 = x^3 + x mod 17 has b = 0, so j = 1728 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(1u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(0u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(1u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(6u32), BigUint::from(17u32))),
+            n: BigUint::from(16u32),
+        };
+        assert_eq!(curve.j_invariant(), Ok(FiniteField::new(BigUint::from(1728u32), curve.p.clone())));
+
+        // y^2 = x^3 mod 17 is singular
+        let singular = EllipticCurve {
+            a: FiniteField::new(BigUint::from(0u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(0u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Identity,
+            n: BigUint::from(1u32),
+        };
+        assert!(singular.j_invariant().is_err());
+    }
+
+    #[test]
+    fn test_multi_scalar_mul() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(5);
+        let pairs: Vec<(BigUint, Point)> = (0..3)
+            .map(|_| {
+                let scalar = rng.gen_biguint_below(&curve.n);
+                let point = curve.scalar_mul(&curve.g, &rng.gen_biguint_below(&curve.n)).unwrap();
+                (scalar, point)
+            })
+            .collect();
+
+        // naive sum of the individual scalar multiplications
+        let mut expected = Point::Identity;
+        for (s, point) in &pairs {
+            let term = curve.scalar_mul(point, s).unwrap();
+            expected = curve.add(&expected, &term).unwrap();
+        }
+
+        assert_eq!(curve.multi_scalar_mul(&pairs), Ok(expected));
+        assert_eq!(curve.multi_scalar_mul(&[]), Ok(Point::Identity));
+
+        let off_curve_point = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(2u32), curve.p.clone()));
+        assert!(curve.multi_scalar_mul(&[(BigUint::from(3u32), off_curve_point)]).is_err());
+    }
+
+    #[test]
+    fn test_double_add_scalars_0_to_30() {
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+
+        // d(5, 1) for d = 1..18, 19(5, 1) = Point::Identity
+        let multiples = [
+            (5, 1), (6, 3), (10, 6), (3, 1), (9, 16), (16, 13), (0, 6), (13, 7), (7, 6),
+            (7, 11), (13, 10), (0, 11), (16, 4), (9, 1), (3, 16), (10, 11), (6, 14), (5, 16),
+        ];
+        let expected = |d: u32| -> Point {
+            match d % 19 {
+                0 => Point::Identity,
+                i => {
+                    let (x, y) = multiples[i as usize - 1];
+                    Point::Coor(FiniteField::new(BigUint::from(x as u32), curve.p.clone()), FiniteField::new(BigUint::from(y as u32), curve.p.clone()))
+                }
+            }
+        };
+
+        for d in 0..=30u32 {
+            assert_eq!(curve.double_add(&curve.g, &BigUint::from(d)), Ok(expected(d)), "d = {}", d);
+            assert_eq!(curve.scalar_mul(&curve.g, &BigUint::from(d)), Ok(expected(d)), "d = {}", d);
+        }
+    }
+
+    #[test]
+    fn test_hash_to_point() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+
+        let point = curve.hash_to_point(b"hello").unwrap();
+        assert!(curve.is_on_curve(&point).unwrap());
+        assert_eq!(curve.hash_to_point(b"hello"), Ok(point.clone()));
+        match &point {
+            Point::Coor(_, y) => assert!(y.is_even()),
+            Point::Identity => panic!("hash_to_point should not return the identity"),
+        }
+
+        let other = curve.hash_to_point(b"world").unwrap();
+        assert!(curve.is_on_curve(&other).unwrap());
+        assert_ne!(point, other);
+    }
+
+    #[test]
+    fn test_mul_precomputed() {
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+
+        let table = curve.precompute_base(&curve.g).unwrap();
+        for d in 0..=40u32 {
+            assert_eq!(curve.mul_precomputed(&table, &BigUint::from(d)), curve.scalar_mul(&curve.g, &BigUint::from(d)), "d = {}", d);
+        }
+    }
+
+    #[test]
+    fn test_point() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let (gx, gy) = curve.g.coords().unwrap();
+        assert_eq!(curve.point(gx.clone(), gy.clone()), Ok(curve.g.clone()));
+
+        assert_eq!(curve.point(gx.clone(), &gy + 1u32), Err("Point is not on the curve"));
+        // y + p would reduce to the same point, but is rejected
+        assert_eq!(curve.point(gx, &gy + &curve.p), Err("Coordinate is not less than p"));
+    }
+
+    #[test]
+    fn test_pippenger() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        // y^2 = x^3 + 2x + 2 mod 17
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p,
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(98);
+        let pairs: Vec<(BigUint, Point)> = (0..64).map(|_| (rng.gen_biguint(40), curve.random_point(&mut rng).unwrap())).collect();
+
+        let mut naive = Point::Identity;
+        for (s, point) in &pairs {
+            naive = curve.add(&naive, &curve.scalar_mul(point, s).unwrap()).unwrap();
+        }
+
+        for window in [1, 3, 4, 8] {
+            assert_eq!(curve.pippenger(&pairs, window), Ok(naive.clone()), "window = {}", window);
+        }
+        assert_eq!(curve.pippenger(&[], 4), Ok(Point::Identity));
+        assert!(curve.pippenger(&pairs, 0).is_err());
+
+        let off_curve = Point::Coor(FiniteField::new(BigUint::from(1u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        assert!(curve.pippenger(&[(BigUint::from(1u32), off_curve)], 4).is_err());
+    }
+
+    #[test]
+    fn test_comb_mul() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let mut scalars = vec![BigUint::from(0u32), BigUint::from(1u32), BigUint::from(2u32), &curve.n - 1u32, curve.n.clone()];
+        let mut s = BigUint::parse_bytes(b"C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5", 16).unwrap();
+        for _ in 0..12 {
+            s = (&s * &s + 7u32) % &curve.n;
+            scalars.push(s.clone());
+        }
+
+        for w in [1, 4, 5] {
+            let table = curve.comb_precompute(&curve.g, w).unwrap();
+            for s in &scalars {
+                assert_eq!(curve.comb_mul(&table, s), curve.scalar_mul(&curve.g, &(s % &curve.n)), "w = {}, s = {:x}", w, s);
+            }
+        }
+
+        // a non-generator base and a width that does not divide bits(n)
+        let base = curve.scalar_mul(&curve.g, &BigUint::from(99u32)).unwrap();
+        let table = curve.comb_precompute(&base, 7).unwrap();
+        assert_eq!(curve.comb_mul(&table, &scalars[6]), curve.scalar_mul(&base, &scalars[6]));
+
+        assert!(curve.comb_precompute(&curve.g, 0).is_err());
+        assert!(curve.comb_precompute(&curve.g, 9).is_err());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_add_and_double_report_the_attempted_point() {
+        // y^2 = x^3 mod 15: 15 is not prime, so the Fermat inverse a^(p-2) is wrong and the slope
+        // with it, even though (1, 1) and (6, 6) lie on the curve
+        let p = BigUint::from(15u32);
+        let field = |v: u32| FiniteField::new(BigUint::from(v), p.clone());
+        let curve = EllipticCurve {
+            a: field(0),
+            b: field(0),
+            g: Point::Coor(field(1), field(1)),
+            n: BigUint::from(15u32),
+            p: p.clone(),
+        };
+
+        let error = curve.double(&curve.g).unwrap_err();
+        assert_eq!(error, CurveError::NotOnCurve { operation: "double", x3: BigUint::from(4u32), y3: BigUint::from(11u32) });
+        assert_eq!(error.to_string(), "Resulting point is not on the curve: double gave x3 = 0x4, y3 = 0xb");
+
+        let error = curve.add(&curve.g, &Point::Coor(field(6), field(6))).unwrap_err();
+        assert_eq!(error.to_string(), "Resulting point is not on the curve: add gave x3 = 0x3, y3 = 0x9");
+
+        // the &'static str APIs built on add keep only the fixed message
+        assert_eq!(curve.scalar_mul(&curve.g, &BigUint::from(2u32)), Err("Resulting point is not on the curve"));
+    }
+
+    #[test]
+    fn test_serialize_point() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+
+        let uncompressed = curve.serialize_point(&curve.g, false).unwrap();
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(uncompressed[0], 0x04);
+        assert_eq!(curve.deserialize_point(&uncompressed), Ok(curve.g.clone()));
+
+        // the generator's y is even
+        let compressed = curve.serialize_point(&curve.g, true).unwrap();
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(compressed[0], 0x02);
+        assert_eq!(curve.deserialize_point(&compressed), Ok(curve.g.clone()));
+
+        let minus_g = match &curve.g {
+            Point::Coor(x, y) => Point::Coor(x.clone(), FiniteField::new(&curve.p - y.get_value(), curve.p.clone())),
+            Point::Identity => unreachable!(),
+        };
+        let compressed = curve.serialize_point(&minus_g, true).unwrap();
+        assert_eq!(compressed[0], 0x03);
+        assert_eq!(curve.deserialize_point(&compressed), Ok(minus_g));
+
+        assert!(curve.serialize_point(&Point::Identity, true).is_err());
+        assert!(curve.deserialize_point(&[0x05; 33]).is_err());
+        assert!(curve.deserialize_point(&uncompressed[..64]).is_err());
+    }
+
+    #[test]
+    fn test_point_accessors() {
+        let x = FiniteField::new(BigUint::from(5u32), BigUint::from(17u32));
+        let y = FiniteField::new(BigUint::from(1u32), BigUint::from(17u32));
+        let point = Point::Coor(x.clone(), y.clone());
+
+        assert!(!point.is_identity());
+        assert_eq!(point.x(), Some(&x));
+        assert_eq!(point.y(), Some(&y));
+
+        assert!(Point::<FiniteField>::Identity.is_identity());
+        assert_eq!(Point::<FiniteField>::Identity.x(), None);
+        assert_eq!(Point::<FiniteField>::Identity.y(), None);
+    }
+
+    #[test]
+    fn test_scalar_mul_x_only() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        for k in [1u64, 2, 3, 123456789, u64::MAX] {
+            let k = BigUint::from(k);
+            let full = curve.scalar_mul(&curve.g, &k).unwrap();
+            assert_eq!(curve.scalar_mul_x_only(&curve.g, &k).as_ref(), Ok(full.x().unwrap()));
+        }
+        assert!(curve.scalar_mul_x_only(&curve.g, &curve.n).is_err());
+    }
+
+    #[test]
+    fn test_coords() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let gx = BigUint::parse_bytes(b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap();
+        let gy = BigUint::parse_bytes(b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap();
+
+        assert_eq!(curve.g.coords(), Some((gx.clone(), gy.clone())));
+        assert_eq!(curve.g.clone().into_coords(), Some((gx, gy)));
+        assert_eq!(Point::Identity.coords(), None);
+        assert_eq!(Point::Identity.into_coords(), None);
+    }
+
+    #[test]
+    fn test_scalar_mul_double_uneven_widths() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let q = curve.scalar_mul(&curve.g, &BigUint::from(7u32)).unwrap();
+
+        // a full-width u1 against a 2-bit u2, and the other way round
+        let big = &curve.n - BigUint::from(2u32);
+        let small = BigUint::from(3u32);
+        for (u1, u2) in [(big.clone(), small.clone()), (small.clone(), big.clone())] {
+            let lhs = curve.scalar_mul(&curve.g, &u1).unwrap();
+            let rhs = curve.scalar_mul(&q, &u2).unwrap();
+            let expected = curve.add(&lhs, &rhs).unwrap();
+            assert_eq!(curve.scalar_mul_double(&u1, &curve.g, &u2, &q), Ok(expected));
+        }
+
+        // a zero scalar leaves just the other term, and P == Q goes through doubling
+        assert_eq!(curve.scalar_mul_double(&BigUint::from(0u32), &curve.g, &small, &q), curve.scalar_mul(&q, &small));
+        assert_eq!(curve.scalar_mul_double(&small, &curve.g, &small, &curve.g), curve.scalar_mul(&curve.g, &BigUint::from(6u32)));
+    }
+
+    #[test]
+    fn test_neg_and_sub() {
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+
+        // -G = 18G = (5, 16)
+        let neg_g = Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(16u32), BigUint::from(17u32)));
+        assert_eq!(curve.neg(&curve.g), Ok(neg_g));
+        assert_eq!(curve.neg(&Point::Identity), Ok(Point::Identity));
+
+        // 5G - 2G = 3G, G - G = O and 2G - (-G) = 3G
+        let two_g = curve.scalar_mul(&curve.g, &BigUint::from(2u32)).unwrap();
+        let three_g = curve.scalar_mul(&curve.g, &BigUint::from(3u32)).unwrap();
+        let five_g = curve.scalar_mul(&curve.g, &BigUint::from(5u32)).unwrap();
+        assert_eq!(curve.sub(&five_g, &two_g), Ok(three_g.clone()));
+        assert_eq!(curve.sub(&curve.g, &curve.g), Ok(Point::Identity));
+        assert_eq!(curve.sub(&two_g, &curve.neg(&curve.g).unwrap()), Ok(three_g));
+    }
+
+    #[test]
+    fn test_scalar_mul_wnaf() {
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+
+        for width in 2..=5 {
+            for d in 0u32..=25 {
+                let s = BigUint::from(d);
+                assert_eq!(curve.scalar_mul_wnaf(&curve.g, &s, width), curve.scalar_mul(&curve.g, &s), "d = {}, width = {}", d, width);
+            }
+        }
+
+        assert!(curve.scalar_mul_wnaf(&curve.g, &BigUint::from(3u32), 1).is_err());
+    }
+
+    #[test]
+    fn test_add_equal_points_doubles() {
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+
+        // every point of the group: P + P = 2P
+        for d in 1u32..19 {
+            let point = curve.scalar_mul(&curve.g, &BigUint::from(d)).unwrap();
+            assert_eq!(curve.add(&point, &point), curve.double(&point), "d = {}", d);
+        }
+        // (5,1) + (5,1) = (6,3)
+        let expected = Point::Coor(FiniteField::new(BigUint::from(6u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(3u32), BigUint::from(17u32)));
+        assert_eq!(curve.add(&curve.g, &curve.g), Ok(expected));
+    }
+
+    #[test]
+    fn test_validate_public_key() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let public_key = curve.scalar_mul(&curve.g, &BigUint::from(123456789u64)).unwrap();
+        assert_eq!(curve.validate_public_key(&public_key), Ok(()));
+
+        assert!(curve.validate_public_key(&Point::Identity).is_err());
+        let off_curve_point = Point::Coor(FiniteField::new(BigUint::from(5u32), curve.p.clone()), FiniteField::new(BigUint::from(2u32), curve.p.clone()));
+        assert!(curve.validate_public_key(&off_curve_point).is_err());
+
+        // y^2 = x^3 + 7 mod 17 has 18 points; G = (5, 8) has order 3 (cofactor 6) and (8, 3) has order 6
+        let p = BigUint::from(17u32);
+        let cofactor_curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(0u32), p.clone()),
+            b: FiniteField::new(BigUint::from(7u32), p.clone()),
+            p: p.clone(),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(8u32), p.clone())),
+            n: BigUint::from(3u32),
+        };
+        let outside_subgroup = Point::Coor(FiniteField::new(BigUint::from(8u32), p.clone()), FiniteField::new(BigUint::from(3u32), p.clone()));
+        assert!(cofactor_curve.validate_public_key(&outside_subgroup).is_err());
+        assert_eq!(cofactor_curve.validate_public_key(&cofactor_curve.g), Ok(()));
+    }
+
+    #[test]
+    fn test_y_parity() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+
+        // G.y = 483ADA77...FB10D4B8 is even, -G.y is odd
+        assert_eq!(curve.g.y_parity(), Some(true));
+        assert_eq!(curve.neg(&curve.g).unwrap().y_parity(), Some(false));
+        assert_eq!(Point::Identity.y_parity(), None);
+
+        // the parity is the bit carried by the compressed prefix
+        let two_g = curve.double(&curve.g).unwrap();
+        let prefix = curve.serialize_point(&two_g, true).unwrap()[0];
+        assert_eq!(two_g.y_parity(), Some(prefix == 0x02));
+
+        assert!(FiniteField::new(BigUint::from(4u32), BigUint::from(7u32)).is_even());
+        assert!(!FiniteField::new(BigUint::from(10u32), BigUint::from(7u32)).is_even());
+    }
+
+    #[test]
+    fn test_scalar_mul_ladder_operation_counts() {
+        use crate::op_counter::{op_counts, reset_op_counts, OpCounts};
+
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let bits = curve.n.bits();
+        let scalars = [
+            BigUint::from(1u32),
+            BigUint::from(0xFFu32),
+            BigUint::from(1u32) << 255u32,
+            &curve.n - 1u32,
+            BigUint::parse_bytes(b"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA", 16).unwrap(),
+        ];
+
+        for s in &scalars {
+            let expected = curve.scalar_mul(&curve.g, s).unwrap();
+
+            reset_op_counts();
+            assert_eq!(curve.scalar_mul_ladder(&curve.g, s), Ok(expected));
+            assert_eq!(op_counts(), OpCounts { additions: bits, doublings: bits }, "s = {:x}", s);
+        }
+    }
+
+    #[test]
+    fn test_scalar_mul_fast_matches_scalar_mul() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+        for d in 0u32..=40 {
+            let s = BigUint::from(d);
+            assert_eq!(curve.scalar_mul_fast(&curve.g, &s), curve.scalar_mul(&curve.g, &s), "d = {}", d);
+        }
+
+        let secp256k1 = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let mut rng = ChaCha20Rng::seed_from_u64(17);
+        let mut scalars = vec![BigUint::from(0u32), BigUint::from(1u32), BigUint::from(2u32), &secp256k1.n - 1u32, secp256k1.n.clone()];
+        scalars.extend((0..20).map(|_| rng.gen_biguint_below(&secp256k1.n)));
+        let point = secp256k1.scalar_mul(&secp256k1.g, &BigUint::from(12345u32)).unwrap();
+        for s in &scalars {
+            assert_eq!(secp256k1.scalar_mul_fast(&point, s), secp256k1.scalar_mul(&point, s), "s = {:x}", s);
+        }
+    }
+
+    #[test]
+    fn test_count_points() {
+        // y^2 = x^3 + 2x + 2 mod 17 has prime order 19, the order of G
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            b: FiniteField::new(BigUint::from(2u32), BigUint::from(17u32)),
+            p: BigUint::from(17u32),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32))),
+            n: BigUint::from(19u32),
+        };
+        assert_eq!(curve.count_points(), Ok(BigUint::from(19u32)));
+
+        // y^2 = x^3 + 7 mod 17 has 18 points
+        let p = BigUint::from(17u32);
+        let other = EllipticCurve {
+            a: FiniteField::new(BigUint::from(0u32), p.clone()),
+            b: FiniteField::new(BigUint::from(7u32), p.clone()),
+            p: p.clone(),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(8u32), p.clone())),
+            n: BigUint::from(3u32),
+        };
+        assert_eq!(other.count_points(), Ok(BigUint::from(18u32)));
+
+        // y^2 = x^3 + x mod 17 has 16 points, x = 0 contributing only (0, 0)
+        let two_torsion = EllipticCurve {
+            a: FiniteField::new(BigUint::from(1u32), p.clone()),
+            b: FiniteField::new(BigUint::from(0u32), p.clone()),
+            p: p.clone(),
+            g: Point::Coor(FiniteField::new(BigUint::from(0u32), p.clone()), FiniteField::new(BigUint::from(0u32), p.clone())),
+            n: BigUint::from(2u32),
+        };
+        assert_eq!(two_torsion.count_points(), Ok(BigUint::from(16u32)));
+
+        let secp256k1 = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        assert!(secp256k1.count_points().is_err());
+    }
+
+    #[test]
+    fn test_twist_order() {
+        // y^2 = x^3 + 2x + 2 mod 17 has 19 points, its twist 2 * 18 - 19 = 17, a 5-bit prime
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p: p.clone(),
+        };
+        assert_eq!(curve.twist_order(), Ok(BigUint::from(17u32)));
+        assert_eq!(curve.is_twist_secure(5), Ok(true));
+        assert_eq!(curve.is_twist_secure(6), Ok(false));
+
+        // y^2 = x^3 + 7 mod 17 has 18 points, so the twist also has 18 = 2 * 3^2
+        let other = EllipticCurve {
+            a: FiniteField::new(BigUint::from(0u32), p.clone()),
+            b: FiniteField::new(BigUint::from(7u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(8u32), p.clone())),
+            n: BigUint::from(3u32),
+            p,
+        };
+        assert_eq!(other.twist_order(), Ok(BigUint::from(18u32)));
+        assert_eq!(other.is_twist_secure(3), Ok(false));
+
+        let secp256k1 = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        assert!(secp256k1.twist_order().is_err());
+    }
+
+    #[test]
+    fn test_order_from_trace() {
+        // y^2 = x^3 + 2x + 2 mod 17 has 19 points, so t = -1
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p,
+        };
+        assert_eq!(curve.order_from_trace(&BigInt::from(-1)), curve.count_points());
+        assert_eq!(curve.order_from_trace(&BigInt::from(8)), Ok(BigUint::from(10u32)));
+        // 2*sqrt(17) < 9
+        assert!(curve.order_from_trace(&BigInt::from(9)).is_err());
+        assert!(curve.order_from_trace(&BigInt::from(-9)).is_err());
+
+        // secp256k1 has cofactor 1, so its order is n
+        let secp256k1 = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let t = BigInt::parse_bytes(b"432420386565659656852420866390673177327", 10).unwrap();
+        assert_eq!(secp256k1.order_from_trace(&t), Ok(secp256k1.n.clone()));
+    }
+
+    #[test]
+    fn test_add_equal_x_unrelated_y() {
+        // (5, 1) is on y^2 = x^3 + 2x + 2 mod 17, (5, 7) is not
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p: p.clone(),
+        };
+        let off_curve = Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(7u32), p));
+
+        assert_eq!(curve.add(&curve.g, &off_curve), Err("Point is not on the curve".into()));
+        // the unchecked path used inside the scalar multiplication loops errors cleanly too
+        assert_eq!(curve.add_unchecked(&curve.g, &off_curve), Err("Points share an x-coordinate but are neither equal nor negations".into()));
+    }
+
+    #[test]
+    fn test_point_equality_ignores_reduction() {
+        let p = BigUint::from(17u32);
+        let reduced = Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone()));
+        let unreduced = Point::Coor(
+            FiniteField { value: BigUint::from(22u32), p: p.clone() },
+            FiniteField { value: BigUint::from(35u32), p: p.clone() },
+        );
+        assert_eq!(reduced, unreduced);
+        assert_ne!(reduced, Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(16u32), p)));
+    }
+
+    #[test]
+    fn test_conditional_swap() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let two_g = curve.double(&curve.g).unwrap();
+
+        for (first, second) in [(curve.g.clone(), two_g.clone()), (curve.g.clone(), Point::Identity), (Point::Identity, two_g.clone()), (Point::Identity, Point::Identity)] {
+            let (mut a, mut b) = (first.clone(), second.clone());
+            Point::conditional_swap(&mut a, &mut b, Choice::from(0));
+            assert_eq!((&a, &b), (&first, &second));
+
+            Point::conditional_swap(&mut a, &mut b, Choice::from(1));
+            assert_eq!((&a, &b), (&second, &first));
+
+            // swapping back is a no-op overall
+            Point::conditional_swap(&mut a, &mut b, Choice::from(1));
+            assert_eq!((&a, &b), (&first, &second));
+        }
+    }
+
+    #[test]
+    fn test_from_named() {
+        let secp256k1 = EllipticCurve::from_named("secp256k1").unwrap();
+        assert_eq!(secp256k1, crate::secp256k1::Secp256k1::new().elliptic_curve);
+        let expected_g = Point::Coor(
+            FiniteField::new(BigUint::parse_bytes(b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap(), secp256k1.p.clone()),
+            FiniteField::new(BigUint::parse_bytes(b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap(), secp256k1.p.clone()),
+        );
+        assert_eq!(secp256k1.g, expected_g);
+
+        // aliases resolve to the same curve, and every generator is on its curve with order n
+        assert_eq!(EllipticCurve::from_named("P-256"), EllipticCurve::from_named("secp256r1"));
+        assert_eq!(EllipticCurve::from_named("prime256v1"), EllipticCurve::from_named("secp256r1"));
+        for name in ["secp256r1", "secp384r1"] {
+            let curve = EllipticCurve::from_named(name).unwrap();
+            assert_eq!(curve.is_on_curve(&curve.g), Ok(true));
+            assert_eq!(curve.scalar_mul_fast(&curve.g, &curve.n), Ok(Point::Identity), "{}", name);
+        }
+
+        assert!(EllipticCurve::from_named("secp999k1").is_err());
+        assert!(EllipticCurve::from_named("").is_err());
+    }
+
+    #[test]
+    fn test_p256_sec1_interop() {
+        // the P-256 key of RFC 7517 A.1 as WebCrypto's exportKey("raw") gives it: 0x04 || x || y
+        let curve = EllipticCurve::from_named("P-256").unwrap();
+        let mut raw = vec![0x04];
+        raw.extend(FiniteField::new(BigUint::parse_bytes(b"30A0424CD21C2944838A2D75C92B37E76EA20D9F00893A3B4EEE8A3C0AAFEC3E", 16).unwrap(), curve.p.clone()).to_bytes_be());
+        raw.extend(FiniteField::new(BigUint::parse_bytes(b"E04B65E92456D9888B52B379BDFBD51EE869EF1F0FC65B6659695B6CCE081723", 16).unwrap(), curve.p.clone()).to_bytes_be());
+        assert_eq!(raw.len(), 65);
+
+        let point = curve.deserialize_point(&raw).unwrap();
+        assert_eq!(curve.is_on_curve(&point), Ok(true));
+        assert_eq!(curve.serialize_point(&point, false), Ok(raw.clone()));
+
+        // y is odd, so the 33-byte compressed form starts with 0x03
+        let compressed = curve.serialize_point(&point, true).unwrap();
+        assert_eq!(compressed.len(), 33);
+        assert_eq!((compressed[0], &compressed[1..]), (0x03, &raw[1..33]));
+        assert_eq!(curve.deserialize_point(&compressed), Ok(point));
+
+        // the same bytes are not a secp256k1 point
+        assert!(crate::secp256k1::Secp256k1::new().elliptic_curve.deserialize_point(&raw).is_err());
+    }
+
+    #[test]
+    fn test_in_place_operations() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let p = curve.scalar_mul(&curve.g, &BigUint::from(12345u32)).unwrap();
+
+        let mut acc = Point::Identity;
+        for _ in 0..3 {
+            curve.add_in_place(&mut acc, &p).unwrap();
+        }
+        assert_eq!(Ok(acc.clone()), curve.scalar_mul(&p, &BigUint::from(3u32)));
+
+        // adding O and doubling match the non-mutating versions
+        let before = acc.clone();
+        curve.add_in_place(&mut acc, &Point::Identity).unwrap();
+        assert_eq!(acc, before);
+        curve.double_in_place(&mut acc).unwrap();
+        assert_eq!(Ok(acc.clone()), curve.double(&before));
+
+        let mut identity = Point::Identity;
+        curve.double_in_place(&mut identity).unwrap();
+        assert_eq!(identity, Point::Identity);
+
+        let off_curve = Point::Coor(FiniteField::new(BigUint::from(1u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        assert!(curve.add_in_place(&mut acc, &off_curve).is_err());
+        assert_eq!(Ok(acc), curve.double(&before));
+    }
+
+    #[test]
+    fn test_scalar_mul_signed() {
+        // y^2 = x^3 + 2x + 2 mod 17, G = (5, 1) of order 19
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p,
+        };
+
+        let three_g = curve.scalar_mul(&curve.g, &BigUint::from(3u32)).unwrap();
+        assert_eq!(curve.scalar_mul_signed(&curve.g, &BigInt::from(-3)), curve.neg(&three_g));
+        assert_eq!(curve.scalar_mul_signed(&curve.g, &BigInt::from(3)), Ok(three_g));
+        assert_eq!(curve.scalar_mul_signed(&curve.g, &BigInt::from(0)), Ok(Point::Identity));
+        // -3 = 16 mod 19
+        assert_eq!(curve.scalar_mul_signed(&curve.g, &BigInt::from(-3)), curve.scalar_mul(&curve.g, &BigUint::from(16u32)));
+        assert_eq!(curve.scalar_mul_signed(&curve.g, &BigInt::from(-19)), Ok(Point::Identity));
+    }
+
+    #[test]
+    fn test_check_order() {
+        for name in ["secp256k1", "P-256", "P-384"] {
+            assert_eq!(EllipticCurve::from_named(name).unwrap().check_order(), Ok(()), "{}", name);
+        }
+
+        // n + 2 is divisible by 3
+        let mut curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        curve.n += 2u32;
+        assert_eq!(curve.check_order(), Err("Group order n is not prime"));
+
+        // 19 is prime, but the order of G = (5, 1) on y^2 = x^3 + 2x + 2 mod 17 is 19, not 17
+        let p = BigUint::from(17u32);
+        let mut toy = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p,
+        };
+        assert_eq!(toy.check_order(), Ok(()));
+        toy.n = BigUint::from(17u32);
+        assert_eq!(toy.check_order(), Err("n*G is not the point at infinity"));
+    }
+
+    #[test]
+    fn test_is_probable_prime() {
+        let primes: Vec<u32> = (0..200u32).filter(|&k| k >= 2 && (2..k).all(|d| k % d != 0)).collect();
+        for k in 0..200u32 {
+            assert_eq!(is_probable_prime(&BigUint::from(k)), primes.contains(&k), "k = {}", k);
+        }
+        // Carmichael number 561 = 3 * 11 * 17 and the strong pseudoprime 3215031751 to bases 2, 3, 5, 7
+        assert!(!is_probable_prime(&BigUint::from(561u32)));
+        assert!(!is_probable_prime(&BigUint::from(3215031751u64)));
+    }
+
+    #[test]
+    fn test_same_params() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let named = EllipticCurve::from_named("secp256k1").unwrap();
+        assert!(curve.same_params(&named));
+        assert_eq!(curve, named);
+
+        // a curve differing only in its group order is not the same curve
+        let mut other_order = named.clone();
+        other_order.n -= 1u32;
+        assert!(!curve.same_params(&other_order));
+        assert_ne!(curve, other_order);
+
+        let mut other_generator = named;
+        other_generator.g = curve.scalar_mul(&curve.g, &BigUint::from(2u32)).unwrap();
+        assert!(!curve.same_params(&other_generator));
+        assert!(!curve.same_params(&EllipticCurve::from_named("P-256").unwrap()));
+    }
+
+    #[test]
+    fn test_lift_x() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let x = curve.g.x().unwrap().clone();
+        let (even, odd) = curve.lift_x(&x).unwrap();
+
+        assert_eq!(curve.is_on_curve(&even), Ok(true));
+        assert_eq!(curve.is_on_curve(&odd), Ok(true));
+        assert_eq!(curve.neg(&even), Ok(odd.clone()));
+        assert_eq!((even.y_parity(), odd.y_parity()), (Some(true), Some(false)));
+        // G has an even y
+        assert_eq!(even, curve.g);
+
+        // 5^3 + 7 is not a square mod p
+        assert!(curve.lift_x(&FiniteField::new(BigUint::from(5u32), curve.p.clone())).is_err());
+        assert!(curve.lift_x(&FiniteField::new(BigUint::from(5u32), BigUint::from(17u32))).is_err());
+    }
+
+    #[test]
+    fn test_random_point() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        // y^2 = x^3 + 2x + 2 mod 17 has 18 affine points
+        let p = BigUint::from(17u32);
+        let curve = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p,
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        let mut seen: Vec<Point> = Vec::new();
+        for _ in 0..1000 {
+            let point = curve.random_point(&mut rng).unwrap();
+            assert_eq!(curve.is_on_curve(&point), Ok(true));
+            if !seen.contains(&point) {
+                seen.push(point);
+            }
+        }
+        // both signs of y come up, so every affine point is reached
+        assert_eq!(seen.len(), 18);
+    }
+
+    #[test]
+    fn test_scalar_mul_unchecked_loop_matches_checked() {
+        let curve = crate::secp256k1::Secp256k1::new().elliptic_curve;
+        let s = BigUint::parse_bytes(b"C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5", 16).unwrap();
+
+        // the same double-add, with add and double validating every intermediate point
+        let mut expected = Point::Identity;
+        for i in (0..s.bits()).rev() {
+            expected = curve.double(&expected).unwrap();
+            if s.bit(i) {
+                expected = curve.add(&expected, &curve.g).unwrap();
+            }
+        }
+
+        let result = curve.scalar_mul(&curve.g, &s).unwrap();
+        assert_eq!(result, expected);
+        assert_eq!(curve.is_on_curve(&result), Ok(true));
+
+        // the input is still checked once
+        let off_curve = Point::Coor(FiniteField::new(BigUint::from(1u32), curve.p.clone()), FiniteField::new(BigUint::from(1u32), curve.p.clone()));
+        assert!(curve.scalar_mul(&off_curve, &s).is_err());
+    }
+
+    // written only against the FieldElement trait
+    fn generic_mul<F: FieldElement>(curve: &EllipticCurve<F>, p: &Point<F>, s: u32) -> Point<F> {
+        let twice = curve.double(p).unwrap();
+        let sum = curve.add(&twice, p).unwrap();
+        assert_eq!(curve.sub(&sum, p), Ok(twice));
+
+        let s = BigUint::from(s);
+        let product = curve.scalar_mul(p, &s).unwrap();
+        let table = curve.precompute_base(p).unwrap();
+        assert_eq!(curve.mul_precomputed(&table, &s).as_ref(), Ok(&product));
+        let comb = curve.comb_precompute(p, 3).unwrap();
+        assert_eq!(curve.comb_mul(&comb, &s).as_ref(), Ok(&product));
+        product
+    }
+
+    #[test]
+    fn test_generic_field_backend() {
+        let p = BigUint::from(17u32);
+        let curve: EllipticCurve<FiniteField> = EllipticCurve {
+            a: FieldElement::from_biguint(BigUint::from(2u32), &p),
+            b: FieldElement::from_biguint(BigUint::from(2u32), &p),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p: p.clone(),
+        };
+        let point = |x: u32, y: u32| Point::Coor(FiniteField::new(BigUint::from(x), p.clone()), FiniteField::new(BigUint::from(y), p.clone()));
+
+        // same results as the FiniteField-specific paths
+        assert_eq!(generic_mul(&curve, &curve.g, 2), point(6, 3));
+        assert_eq!(generic_mul(&curve, &curve.g, 7), point(0, 6));
+        assert_eq!(generic_mul(&curve, &curve.g, 19), Point::Identity);
+        for s in 0..19u32 {
+            assert_eq!(Ok(generic_mul(&curve, &curve.g, s)), curve.scalar_mul_fast(&curve.g, &BigUint::from(s)));
+        }
+    }
+
+    // implements only the required FieldElement methods, so double, mul_small, sub_assign and
+    // mul_assign all come from the trait's default bodies
+    #[derive(Clone, PartialEq, Debug)]
+    struct MinimalField(FiniteField);
+
+    impl FieldElement for MinimalField {
+        fn from_biguint(value: BigUint, p: &BigUint) -> Self {
+            MinimalField(FiniteField::new(value, p.clone()))
+        }
+
+        fn to_biguint(&self) -> BigUint {
+            self.0.to_biguint()
+        }
+
+        fn in_field(&self, p: &BigUint) -> bool {
+            self.0.in_field(p)
+        }
+
+        fn is_zero(&self) -> bool {
+            FieldElement::is_zero(&self.0)
+        }
+
+        fn add(&self, other: &Self) -> Result<Self, &'static str> {
+            FieldElement::add(&self.0, &other.0).map(MinimalField)
+        }
+
+        fn sub(&self, other: &Self) -> Result<Self, &'static str> {
+            FieldElement::sub(&self.0, &other.0).map(MinimalField)
+        }
+
+        fn mul(&self, other: &Self) -> Result<Self, &'static str> {
+            FieldElement::mul(&self.0, &other.0).map(MinimalField)
+        }
+
+        fn neg(&self) -> Self {
+            MinimalField(FieldElement::neg(&self.0))
+        }
+
+        fn inverse(&self) -> Result<Self, &'static str> {
+            FieldElement::inverse(&self.0).map(MinimalField)
+        }
+    }
+
+    #[test]
+    fn test_default_field_element_methods() {
+        let p = BigUint::from(17u32);
+        let element = |v: u32| MinimalField::from_biguint(BigUint::from(v), &p);
+
+        for value in 0..17u32 {
+            let a = element(value);
+            assert_eq!(a.double(), Ok(element(2 * value)), "a = {}", value);
+            for k in [0u32, 1, 2, 3, 5, 17 * 5 + 2] {
+                assert_eq!(a.mul_small(k).map(|r| r.0), Ok(a.0.mul_small(k)), "a = {}, k = {}", value, k);
+            }
+
+            let mut b = a.clone();
+            b.sub_assign(&element(5)).unwrap();
+            b.mul_assign(&element(3)).unwrap();
+            assert_eq!(b, element((value + 12) * 3));
+        }
+
+        // the curve code doubles through mul_small(3), so the group law must match FiniteField
+        let curve = EllipticCurve {
+            a: element(2),
+            b: element(2),
+            g: Point::Coor(element(5), element(1)),
+            n: BigUint::from(19u32),
+            p: p.clone(),
+        };
+        let reference = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p: p.clone(),
+        };
+        for s in 0..19u32 {
+            let expected = match reference.scalar_mul(&reference.g, &BigUint::from(s)).unwrap() {
+                Point::Coor(x, y) => Point::Coor(MinimalField(x), MinimalField(y)),
+                Point::Identity => Point::Identity,
+            };
+            assert_eq!(generic_mul(&curve, &curve.g, s), expected, "s = {}", s);
+        }
+    }
+
+    #[test]
+    fn test_mul_generator() {
+        let p = BigUint::from(17u32);
+        let toy = EllipticCurve {
+            a: FiniteField::new(BigUint::from(2u32), p.clone()),
+            b: FiniteField::new(BigUint::from(2u32), p.clone()),
+            g: Point::Coor(FiniteField::new(BigUint::from(5u32), p.clone()), FiniteField::new(BigUint::from(1u32), p.clone())),
+            n: BigUint::from(19u32),
+            p,
+        };
+        let secp256k1 = crate::secp256k1::Secp256k1::new().elliptic_curve;
+
+        for curve in [&toy, &secp256k1] {
+            for k in [0u64, 1, 2, 7, 18, 19, 123456789] {
+                let k = BigUint::from(k);
+                assert_eq!(curve.mul_generator(&k), curve.scalar_mul(&curve.g, &k));
+            }
+        }
+    }
+}
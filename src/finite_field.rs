@@ -1,48 +1,132 @@
 use num_bigint::{BigUint};
+use num_traits::Zero;
+use crate::secp256k1;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct FiniteField {
     pub value: BigUint,
     pub p: BigUint,
 }
 
 impl FiniteField {
-    // since value is an element of Fp, value should be less than p 
+    // value is reduced mod p, so any integer (e.g. a full hash digest) maps into Fp
     pub fn new(value: BigUint, p:BigUint) -> Self {
-
-        // println!("Creating FiniteField with value: {:?}, p: {:?}", value, p);
-    
-        assert!(value < p, "Value should be less than p");
         Self {value: value % &p, p}
     }
 
+    // a signed integer mod p: v mod p for v >= 0, p - (|v| mod p) for negative v
+    // (e.g. the a = -3 of the NIST curves)
+    pub fn from_i64(v: i64, p: &BigUint) -> Self {
+        let magnitude = FiniteField::new(BigUint::from(v.unsigned_abs()), p.clone());
+        if v < 0 { magnitude.neg() } else { magnitude }
+    }
+
+    // skips the reduction when the caller guarantees value < p
+    pub fn new_reduced(value: BigUint, p: BigUint) -> Self {
+        debug_assert!(value < p, "Value should be less than p");
+        Self {value, p}
+    }
+
+    // checked constructor for untrusted input: p must be at least 2 and value already in [0, p)
+    // new is for internal call sites, where reducing silently is intended and p is known good
+    pub fn try_new(value: BigUint, p: BigUint) -> Result<Self, &'static str> {
+        if p < BigUint::from(2u32) {
+            return Err("Modulus p must be at least 2");
+        }
+        if value >= p {
+            return Err("Value must be less than p");
+        }
+        Ok(Self {value, p})
+    }
+
     pub fn get_value(&self) -> &BigUint {
         &self.value
     }
 
+    // parity of the canonical representative in [0, p), the bit SEC1 and BIP-340 encode for y
+    pub fn is_even(&self) -> bool {
+        !self.value.bit(0)
+    }
+
+    // fixed-width big-endian encoding, left-padded to ceil(bits(p) / 8) bytes
+    // so every element of the same field encodes to the same length
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let size = self.p.bits().div_ceil(8) as usize;
+        let bytes = self.value.to_bytes_be();
+        let mut padded = vec![0u8; size.saturating_sub(bytes.len())];
+        padded.extend(bytes);
+        padded
+    }
+
+    // big-endian bytes (padded or not) reduced mod p
+    pub fn from_bytes_be(bytes: &[u8], p: BigUint) -> Self {
+        FiniteField::new(BigUint::from_bytes_be(bytes), p)
+    }
+
     // add two elements of Fp
     // (a + b) mod p
     pub fn add(&self, other: &FiniteField) -> Result<FiniteField, &'static str> {
         if self.p != other.p {
             return Err("Operands must be from the same field (p should be the same)");
         }
-        Ok(FiniteField {
-            value: (&self.value + &other.value) % &self.p,
-            p: self.p.clone(),
-        })
+        Ok(FiniteField::new_reduced((&self.value + &other.value) % &self.p, self.p.clone()))
     }
     
     // subtract two elements of Fp
-    // to ensure a - b is positive, add p to a - b
-    // (a + p - b) mod p
+    // a - b when b <= a, otherwise a + p - b, see sub_mod
     pub fn sub(&self, other: &FiniteField) -> Result<FiniteField, &'static str> {
         if self.p != other.p {
             return Err("Operands must be from the same field (p should be the same)");
         }
-        Ok(FiniteField {
-            value: (&self.value + &self.p - &other.value) % &self.p,
-            p: self.p.clone(),
-        })
+        Ok(FiniteField::new_reduced(sub_mod(&self.value, &other.value, &self.p), self.p.clone()))
+    }
+
+    // a -= b in place, the same steps as sub_mod but reusing a's buffer
+    pub fn sub_assign(&mut self, other: &FiniteField) -> Result<(), &'static str> {
+        if self.p != other.p {
+            return Err("Operands must be from the same field (p should be the same)");
+        }
+        if self.value >= self.p {
+            self.value %= &self.p;
+        }
+        let b_reduced;
+        let b = if other.value < self.p { &other.value } else { b_reduced = &other.value % &self.p; &b_reduced };
+        if b > &self.value {
+            self.value += &self.p;
+        }
+        self.value -= b;
+        Ok(())
+    }
+
+    // a *= b in place; num-bigint still allocates the product, which then replaces a's value
+    pub fn mul_assign(&mut self, other: &FiniteField) -> Result<(), &'static str> {
+        if self.p != other.p {
+            return Err("Operands must be from the same field (p should be the same)");
+        }
+        self.value *= &other.value;
+        self.value %= &self.p;
+        Ok(())
+    }
+
+    // 2a mod p, a shift and at most one subtraction instead of a multiplication by a 2 element
+    pub fn double(&self) -> FiniteField {
+        let doubled = (&self.value % &self.p) << 1;
+        let reduced = if doubled >= self.p { doubled - &self.p } else { doubled };
+        FiniteField::new_reduced(reduced, self.p.clone())
+    }
+
+    // k*a mod p for a small constant k, without building k as a field element
+    pub fn mul_small(&self, k: u32) -> FiniteField {
+        FiniteField::new(&self.value * k, self.p.clone())
+    }
+
+    // additive inverse, (p - a) mod p so that zero stays zero
+    pub fn neg(&self) -> FiniteField {
+        FiniteField::new(&self.p - &self.value % &self.p, self.p.clone())
     }
 
     // multiply two elements of Fp
@@ -51,15 +135,95 @@ impl FiniteField {
         if self.p != other.p {
             return Err("Operands must be from the same field (p should be the same)");
         }
-        Ok(FiniteField {
-            value: (&self.value * &other.value) % &self.p,
-            p: self.p.clone(),
-        })
+        Ok(FiniteField::new_reduced((&self.value * &other.value) % &self.p, self.p.clone()))
     }
 
-    // divide two elements of Fp
+    // multiplicative inverse of an element of Fp
     // by Fermat's little theorem, a^(p-1) = 1 mod p s.t. gcd(a,p) = 1
     // thus, a^(p-2) = a^-1 mod p
+    pub fn inverse(&self) -> Result<FiniteField, &'static str> {
+        if self.value == BigUint::from(0u32) {
+            return Err("Cannot invert zero");
+        }
+        if secp256k1::is_field_prime(&self.p) {
+            return secp256k1::secp256k1_field_inverse(self);
+        }
+
+        let exponent = &self.p - BigUint::from(2u32);
+        Ok(FiniteField::new_reduced(self.value.modpow(&exponent, &self.p), self.p.clone()))
+    }
+
+    // a^e mod p
+    pub fn pow(&self, exponent: &BigUint) -> FiniteField {
+        FiniteField::new_reduced(self.value.modpow(exponent, &self.p), self.p.clone())
+    }
+
+    // Legendre symbol (a/p) by Euler's criterion a^((p-1)/2) mod p:
+    // 1 for a non-zero quadratic residue, -1 for a non-residue and 0 for zero (p an odd prime)
+    pub fn legendre_symbol(&self) -> i8 {
+        if self.value == BigUint::from(0u32) {
+            return 0;
+        }
+        let exponent = (&self.p - 1u32) >> 1;
+        if self.value.modpow(&exponent, &self.p) == BigUint::from(1u32) { 1 } else { -1 }
+    }
+
+    // square root in Fp, None when the value is a quadratic non-residue
+    // uses a^((p+1)/4) when p = 3 mod 4, otherwise Tonelli-Shanks (p must be an odd prime)
+    pub fn sqrt(&self) -> Option<FiniteField> {
+        let one = BigUint::from(1u32);
+        if self.value == BigUint::from(0u32) {
+            return Some(self.clone());
+        }
+
+        if self.legendre_symbol() != 1 {
+            return None;
+        }
+        let p_minus_one = &self.p - 1u32;
+
+        if &self.p % 4u32 == BigUint::from(3u32) {
+            let exponent = (&self.p + 1u32) >> 2;
+            return Some(self.pow(&exponent));
+        }
+
+        // p - 1 = q * 2^m with q odd
+        let mut m = p_minus_one.trailing_zeros()?;
+        let q = &p_minus_one >> m;
+
+        // any quadratic non-residue z
+        let z = smallest_non_residue(&self.p)?;
+
+        let mut c = z.modpow(&q, &self.p);
+        let mut t = self.value.modpow(&q, &self.p);
+        let mut r = self.value.modpow(&((&q + 1u32) >> 1), &self.p);
+
+        while t != one {
+            // least i with t^(2^i) = 1
+            let mut i = 0;
+            let mut t_pow = t.clone();
+            while t_pow != one {
+                t_pow = &t_pow * &t_pow % &self.p;
+                i += 1;
+            }
+
+            let b = c.modpow(&(BigUint::from(1u32) << (m - i - 1)), &self.p);
+            m = i;
+            c = &b * &b % &self.p;
+            t = t * &c % &self.p;
+            r = r * &b % &self.p;
+        }
+
+        Some(FiniteField::new_reduced(r, self.p.clone()))
+    }
+
+    // sqrt(u / v), see SqrtRatio; the constants for u's modulus are built on every call (a
+    // primality test and a few exponentiations), so repeated callers should keep a SqrtRatio
+    pub fn sqrt_ratio(u: &FiniteField, v: &FiniteField) -> Result<(bool, FiniteField), &'static str> {
+        SqrtRatio::new(&u.p)?.sqrt_ratio(u, v)
+    }
+
+    // divide two elements of Fp
+    // a / b = a * b^-1 mod p
     pub fn div(&self, other: &FiniteField) -> Result<FiniteField, &'static str> {
         if self.p != other.p {
             return Err("Operands must be from the same field (p should be the same)");
@@ -67,19 +231,363 @@ impl FiniteField {
         if other.value == BigUint::from(0u32) {
             return Err("Cannot divide by zero")
         }
-        
-        let exponent = &self.p - BigUint::from(2u32);
-        Ok(FiniteField {
-            value: (&self.value * &other.value.modpow(&exponent, &self.p)) % &self.p,
-            p: self.p.clone(),
+
+        self.mul(&other.inverse()?)
+    }
+}
+
+// equality of the residues mod p, so an element built directly with an unreduced value
+// (p + 3 rather than 3) still equals the canonical one, and so do points built from them
+impl PartialEq for FiniteField {
+    fn eq(&self, other: &Self) -> bool {
+        if self.p != other.p {
+            return false;
+        }
+        if self.value < self.p && other.value < other.p {
+            return self.value == other.value;
+        }
+        &self.value % &self.p == &other.value % &other.p
+    }
+}
+
+impl Eq for FiniteField {}
+
+// total order by residue, for canonical ordering of elements and points (e.g. sorting public keys
+// by x then y); elements of different fields have no meaningful order, they are only kept
+// consistent with Eq by falling back to comparing the moduli
+impl Ord for FiniteField {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let by_residue = if self.value < self.p && other.value < other.p {
+            self.value.cmp(&other.value)
+        } else {
+            (&self.value % &self.p).cmp(&(&other.value % &other.p))
+        };
+        by_residue.then_with(|| self.p.cmp(&other.p))
+    }
+}
+
+impl PartialOrd for FiniteField {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// raw value of a field element
+impl From<FiniteField> for BigUint {
+    fn from(element: FiniteField) -> Self {
+        element.value
+    }
+}
+
+impl From<&FiniteField> for BigUint {
+    fn from(element: &FiniteField) -> Self {
+        element.value.clone()
+    }
+}
+
+// big-endian bytes of the value (minimal length, no padding)
+impl From<FiniteField> for Vec<u8> {
+    fn from(element: FiniteField) -> Self {
+        element.value.to_bytes_be()
+    }
+}
+
+// the operations the curve arithmetic needs from a field backend, so EllipticCurve and Point
+// can run over something faster than BigUint without changing the curve logic
+pub trait FieldElement: Clone + PartialEq + Debug {
+    // the element value mod p
+    fn from_biguint(value: BigUint, p: &BigUint) -> Self;
+    fn to_biguint(&self) -> BigUint;
+    // whether this is a reduced element of the field with modulus p
+    fn in_field(&self, p: &BigUint) -> bool;
+    fn is_zero(&self) -> bool;
+    fn add(&self, other: &Self) -> Result<Self, &'static str>;
+    fn sub(&self, other: &Self) -> Result<Self, &'static str>;
+    fn mul(&self, other: &Self) -> Result<Self, &'static str>;
+    fn neg(&self) -> Self;
+    fn inverse(&self) -> Result<Self, &'static str>;
+
+    fn div(&self, other: &Self) -> Result<Self, &'static str> {
+        if other.is_zero() {
+            return Err("Cannot divide by zero");
+        }
+        self.mul(&other.inverse()?)
+    }
+
+    // 2a and k*a, overridable so backends need not build small constants as field elements
+    fn double(&self) -> Result<Self, &'static str> {
+        self.add(self)
+    }
+
+    // a -= b and a *= b, overridable so backends can update their storage in place
+    fn sub_assign(&mut self, other: &Self) -> Result<(), &'static str> {
+        *self = self.sub(other)?;
+        Ok(())
+    }
+
+    fn mul_assign(&mut self, other: &Self) -> Result<(), &'static str> {
+        *self = self.mul(other)?;
+        Ok(())
+    }
+
+    fn mul_small(&self, k: u32) -> Result<Self, &'static str> {
+        let mut res = self.sub(self)?;
+        for i in (0..u32::BITS - k.leading_zeros()).rev() {
+            res = res.double()?;
+            if (k >> i) & 1 == 1 {
+                res = res.add(self)?;
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl FieldElement for FiniteField {
+    fn from_biguint(value: BigUint, p: &BigUint) -> Self {
+        FiniteField::new(value, p.clone())
+    }
+
+    fn to_biguint(&self) -> BigUint {
+        self.value.clone()
+    }
+
+    fn in_field(&self, p: &BigUint) -> bool {
+        self.p == *p && self.value < self.p
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == BigUint::from(0u32)
+    }
+
+    fn add(&self, other: &Self) -> Result<Self, &'static str> {
+        FiniteField::add(self, other)
+    }
+
+    fn sub(&self, other: &Self) -> Result<Self, &'static str> {
+        FiniteField::sub(self, other)
+    }
+
+    fn mul(&self, other: &Self) -> Result<Self, &'static str> {
+        FiniteField::mul(self, other)
+    }
+
+    fn neg(&self) -> Self {
+        FiniteField::neg(self)
+    }
+
+    fn inverse(&self) -> Result<Self, &'static str> {
+        FiniteField::inverse(self)
+    }
+
+    fn div(&self, other: &Self) -> Result<Self, &'static str> {
+        FiniteField::div(self, other)
+    }
+
+    fn double(&self) -> Result<Self, &'static str> {
+        Ok(FiniteField::double(self))
+    }
+
+    fn mul_small(&self, k: u32) -> Result<Self, &'static str> {
+        Ok(FiniteField::mul_small(self, k))
+    }
+
+    fn sub_assign(&mut self, other: &Self) -> Result<(), &'static str> {
+        FiniteField::sub_assign(self, other)
+    }
+
+    fn mul_assign(&mut self, other: &Self) -> Result<(), &'static str> {
+        FiniteField::mul_assign(self, other)
+    }
+}
+
+// sqrt(u / v) without inverting v first (RFC 9380 F.2.1.1, sqrt_ratio): (true, sqrt(u/v)) when
+// u/v is a square, otherwise (false, sqrt(Z * u/v)) with Z the smallest non-residue mod p
+// the constants depend only on p and are computed once here, after which a call costs one
+// exponentiation by c3 plus c1 - 1 short ones, less than an inversion plus a square root
+#[derive(Clone, Debug)]
+pub struct SqrtRatio {
+    p: BigUint,
+    // p - 1 = c2 * 2^c1 with c2 odd
+    c1: u64,
+    c3: BigUint,
+    c4: BigUint,
+    c5: BigUint,
+    c6: BigUint,
+    c7: BigUint,
+}
+
+impl SqrtRatio {
+    // p must be an odd prime, anything else is rejected before the constants are derived
+    pub fn new(p: &BigUint) -> Result<Self, &'static str> {
+        if !p.bit(0) || !is_probable_prime(p) {
+            return Err("Modulus must be an odd prime");
+        }
+        let one = BigUint::from(1u32);
+        let z = smallest_non_residue(p).ok_or("Modulus has no quadratic non-residue")?;
+
+        let p_minus_one = p - 1u32;
+        let c1 = p_minus_one.trailing_zeros().ok_or("Modulus must be an odd prime")?;
+        let c2 = &p_minus_one >> c1;
+        Ok(SqrtRatio {
+            p: p.clone(),
+            c1,
+            c3: (&c2 - 1u32) >> 1,
+            c4: (&one << c1) - 1u32,
+            c5: &one << (c1 - 1),
+            c6: z.modpow(&c2, p),
+            c7: z.modpow(&((&c2 + 1u32) >> 1), p),
         })
+    }
+
+    pub fn sqrt_ratio(&self, u: &FiniteField, v: &FiniteField) -> Result<(bool, FiniteField), &'static str> {
+        if u.p != self.p || v.p != self.p {
+            return Err("Operands must be from the same field (p should be the same)");
+        }
+        if v.value.is_zero() {
+            return Err("Cannot divide by zero");
+        }
+        let p = &self.p;
+        let (u_value, v_value) = (&u.value % p, &v.value % p);
+        if u_value.is_zero() {
+            return Ok((true, FiniteField::new_reduced(u_value, p.clone())));
+        }
+
+        let one = BigUint::from(1u32);
+        let mut tv1 = self.c6.clone();
+        let tv2 = v_value.modpow(&self.c4, p);
+        let tv3 = &tv2 * &tv2 % p * &v_value % p;
+        let tv5 = (&u_value * tv3 % p).modpow(&self.c3, p) * &tv2 % p;
+        let tv2 = &tv5 * &v_value % p;
+        let mut tv3 = &tv5 * &u_value % p;
+        let mut tv4 = &tv3 * &tv2 % p;
+
+        let is_qr = tv4.modpow(&self.c5, p) == one;
+        if !is_qr {
+            tv3 = tv3 * &self.c7 % p;
+            tv4 = tv4 * &tv1 % p;
+        }
+
+        for k in (2..=self.c1).rev() {
+            let e1 = tv4.modpow(&(&one << (k - 2)), p) == one;
+            let tv2 = &tv3 * &tv1 % p;
+            tv1 = &tv1 * &tv1 % p;
+            if !e1 {
+                tv3 = tv2;
+                tv4 = tv4 * &tv1 % p;
+            }
+        }
+
+        Ok((is_qr, FiniteField::new_reduced(tv3, p.clone())))
+    }
+}
+
+// Fp as a context, every element built with element() is known to share p
+// so the arithmetic below skips the runtime modulus check (debug-asserted only)
+#[derive(PartialEq, Clone, Debug)]
+pub struct Field {
+    pub p: BigUint,
+}
+
+impl Field {
+    pub fn new(p: BigUint) -> Self {
+        Self {p}
+    }
+
+    pub fn element(&self, value: BigUint) -> FiniteField {
+        FiniteField::new(value, self.p.clone())
+    }
+
+    // (a + b) mod p
+    pub fn add(&self, a: &FiniteField, b: &FiniteField) -> FiniteField {
+        debug_assert!(a.p == self.p && b.p == self.p, "Operands must be elements of this field");
+        FiniteField::new_reduced((&a.value + &b.value) % &self.p, self.p.clone())
+    }
+
+    // (a - b) mod p
+    pub fn sub(&self, a: &FiniteField, b: &FiniteField) -> FiniteField {
+        debug_assert!(a.p == self.p && b.p == self.p, "Operands must be elements of this field");
+        FiniteField::new_reduced(sub_mod(&a.value, &b.value, &self.p), self.p.clone())
+    }
+
+    // (a * b) mod p
+    pub fn mul(&self, a: &FiniteField, b: &FiniteField) -> FiniteField {
+        debug_assert!(a.p == self.p && b.p == self.p, "Operands must be elements of this field");
+        FiniteField::new_reduced((&a.value * &b.value) % &self.p, self.p.clone())
+    }
+}
+
+// least z >= 2 with z^((p-1)/2) = -1 mod p, None when there is none below p
+fn smallest_non_residue(p: &BigUint) -> Option<BigUint> {
+    let p_minus_one = p - 1u32;
+    let half = &p_minus_one >> 1;
+    let mut z = BigUint::from(2u32);
+    while z.modpow(&half, p) != p_minus_one {
+        z += 1u32;
+        if &z >= p {
+            return None;
+        }
+    }
+    Some(z)
+}
+
+// Miller-Rabin with the first twelve primes as bases: exact below 3.3 * 10^24 and, for the
+// sizes of real group orders, wrong with probability at most 4^-12 even for adversarial n
+pub(crate) fn is_probable_prime(n: &BigUint) -> bool {
+    const BASES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    let one = BigUint::from(1u32);
+    if n < &BigUint::from(2u32) {
+        return false;
+    }
+    for base in BASES {
+        if n == &BigUint::from(base) {
+            return true;
+        }
+        if (n % base) == BigUint::from(0u32) {
+            return false;
+        }
+    }
+
+    // n - 1 = d * 2^r with d odd
+    let n_minus_one = n - 1u32;
+    let r = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> r;
+
+    BASES.iter().all(|base| {
+        let mut x = BigUint::from(*base).modpow(&d, n);
+        if x == one || x == n_minus_one {
+            return true;
+        }
+        for _ in 1..r {
+            x = &x * &x % n;
+            if x == n_minus_one {
+                return true;
+            }
+        }
+        false
+    })
 }
+
+// (a - b) mod p without the always-add-p intermediate of (a + p - b) mod p
+// the value fields are public, so a and b are reduced first when they are not already below p;
+// after that adding p once is enough when b > a, and a - b is already in [0, p) otherwise
+fn sub_mod(a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint {
+    let a_reduced;
+    let a = if a < p { a } else { a_reduced = a % p; &a_reduced };
+    let b_reduced;
+    let b = if b < p { b } else { b_reduced = b % p; &b_reduced };
+
+    if b > a {
+        a + p - b
+    } else {
+        a - b
+    }
 }
 
 // Test cases for FiniteField
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::elliptic_curve::Point;
 
     #[test]
     fn test_add() {
@@ -99,6 +607,36 @@ mod tests {
         assert_eq!(a.sub(&b), Ok(c));
     }
 
+    #[test]
+    fn test_sub_mod_17() {
+        let p = BigUint::from(17u32);
+        let f = |value: u32| FiniteField::new(BigUint::from(value), p.clone());
+
+        // b > a wraps around by exactly one p, a = b gives zero, b = 0 leaves a unchanged
+        assert_eq!(f(3).sub(&f(10)).unwrap().value, BigUint::from(10u32));
+        assert_eq!(f(16).sub(&f(16)).unwrap().value, BigUint::from(0u32));
+        assert_eq!(f(9).sub(&f(0)).unwrap().value, BigUint::from(9u32));
+        assert_eq!(f(0).sub(&f(16)).unwrap().value, BigUint::from(1u32));
+
+        // operands whose public value was left unreduced still give a reduced difference
+        let unreduced = |value: u32| FiniteField {value: BigUint::from(value), p: p.clone()};
+        assert_eq!(f(3).sub(&unreduced(27)).unwrap().value, BigUint::from(10u32));
+        assert_eq!(unreduced(20).sub(&f(3)).unwrap().value, BigUint::from(0u32));
+        assert_eq!(unreduced(40).sub(&unreduced(57)).unwrap().value, BigUint::from(0u32));
+        assert_eq!(Field::new(p.clone()).sub(&f(3), &unreduced(35)).value, BigUint::from(2u32));
+    }
+
+    #[test]
+    fn test_neg() {
+        let p = BigUint::from(17u32);
+        for value in [0u32, 1, 5, 16] {
+            let a = FiniteField::new(BigUint::from(value), p.clone());
+            assert_eq!(a.add(&a.neg()), Ok(FiniteField::new(BigUint::from(0u32), p.clone())));
+        }
+        assert_eq!(FiniteField::new(BigUint::from(5u32), p.clone()).neg(), FiniteField::new(BigUint::from(12u32), p.clone()));
+        assert_eq!(FiniteField::new(BigUint::from(0u32), p.clone()).neg().get_value(), &BigUint::from(0u32));
+    }
+
     #[test]
     fn test_mul() {
         let a = FiniteField::new(BigUint::from(2u32), BigUint::from(7u32));
@@ -116,4 +654,326 @@ mod tests {
 
         assert_eq!(a.div(&b), Ok(c));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_div_zero_numerator() {
+        let zero = FiniteField::new(BigUint::from(0u32), BigUint::from(7u32));
+        let b = FiniteField::new(BigUint::from(4u32), BigUint::from(7u32));
+
+        assert_eq!(zero.div(&b), Ok(zero.clone()));
+        assert!(zero.div(&zero).is_err());
+    }
+
+    #[test]
+    fn test_new_reduces_mod_p() {
+        let a = FiniteField::new(BigUint::from(23u32), BigUint::from(7u32));
+        let b = FiniteField::new(BigUint::from(2u32), BigUint::from(7u32));
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_eq_ignores_reduction() {
+        let p = BigUint::from(17u32);
+        let unreduced = FiniteField { value: BigUint::from(20u32), p: p.clone() };
+        assert_eq!(unreduced, FiniteField::new(BigUint::from(3u32), p.clone()));
+        assert_ne!(unreduced, FiniteField::new(BigUint::from(4u32), p));
+        assert_ne!(FiniteField::new(BigUint::from(3u32), BigUint::from(17u32)), FiniteField::new(BigUint::from(3u32), BigUint::from(19u32)));
+    }
+
+    #[test]
+    fn test_sort_by_value() {
+        let p = BigUint::from(17u32);
+        let mut elements: Vec<FiniteField> = [9u32, 0, 16, 4, 11].iter().map(|v| FiniteField::new(BigUint::from(*v), p.clone())).collect();
+        // 20 = 3 mod 17 sorts as 3
+        elements.push(FiniteField { value: BigUint::from(20u32), p: p.clone() });
+        elements.sort();
+
+        let values: Vec<BigUint> = elements.iter().map(|e| &e.value % &p).collect();
+        assert_eq!(values, [0u32, 3, 4, 9, 11, 16].map(BigUint::from));
+
+        // points sort with the identity first, then by x, then by y
+        let point = |x: u32, y: u32| Point::Coor(FiniteField::new(BigUint::from(x), p.clone()), FiniteField::new(BigUint::from(y), p.clone()));
+        let mut points = vec![point(5, 16), Point::Identity, point(3, 1), point(5, 1)];
+        points.sort();
+        assert_eq!(points, vec![Point::Identity, point(3, 1), point(5, 1), point(5, 16)]);
+    }
+
+    #[test]
+    fn test_from_i64() {
+        let p = BigUint::from(17u32);
+        let three = FiniteField::new(BigUint::from(3u32), p.clone());
+        assert_eq!(FiniteField::from_i64(-3, &p).value, BigUint::from(14u32));
+        assert_eq!(FiniteField::from_i64(-3, &p).add(&three).unwrap().value, BigUint::from(0u32));
+        assert_eq!(FiniteField::from_i64(3, &p), three);
+        assert_eq!(FiniteField::from_i64(-20, &p).value, BigUint::from(14u32));
+        assert_eq!(FiniteField::from_i64(-34, &p).value, BigUint::from(0u32));
+        // -2^63 = 8 mod 17, without overflowing on |i64::MIN|
+        assert_eq!(FiniteField::from_i64(i64::MIN, &p).value, BigUint::from(8u32));
+
+        // P-256's a = -3 is the p - 3 of its curve constants
+        let p256 = crate::elliptic_curve::EllipticCurve::from_named("P-256").unwrap();
+        assert_eq!(FiniteField::from_i64(-3, &p256.p), p256.a);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let p = BigUint::from(17u32);
+        assert_eq!(FiniteField::try_new(BigUint::from(16u32), p.clone()), Ok(FiniteField::new(BigUint::from(16u32), p.clone())));
+        assert_eq!(FiniteField::try_new(BigUint::from(17u32), p.clone()), Err("Value must be less than p"));
+        assert_eq!(FiniteField::try_new(BigUint::from(40u32), p), Err("Value must be less than p"));
+        assert_eq!(FiniteField::try_new(BigUint::from(0u32), BigUint::from(0u32)), Err("Modulus p must be at least 2"));
+        assert_eq!(FiniteField::try_new(BigUint::from(0u32), BigUint::from(1u32)), Err("Modulus p must be at least 2"));
+    }
+
+    #[test]
+    fn test_new_reduced() {
+        let a = FiniteField::new_reduced(BigUint::from(5u32), BigUint::from(7u32));
+        let b = FiniteField::new(BigUint::from(5u32), BigUint::from(7u32));
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let p = BigUint::from(17u32);
+        let one = FiniteField::new(BigUint::from(1u32), p.clone());
+
+        for value in 1..17u32 {
+            let a = FiniteField::new(BigUint::from(value), p.clone());
+            assert_eq!(a.mul(&a.inverse().unwrap()), Ok(one.clone()));
+        }
+
+        let zero = FiniteField::new(BigUint::from(0u32), p.clone());
+        assert!(zero.inverse().is_err());
+    }
+
+    #[test]
+    fn test_pow() {
+        let a = FiniteField::new(BigUint::from(3u32), BigUint::from(17u32));
+
+        assert_eq!(a.pow(&BigUint::from(4u32)), FiniteField::new(BigUint::from(13u32), BigUint::from(17u32)));
+        assert_eq!(a.pow(&BigUint::from(0u32)), FiniteField::new(BigUint::from(1u32), BigUint::from(17u32)));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        // 17 = 1 mod 4 exercises Tonelli-Shanks, 19 = 3 mod 4 the direct exponent
+        for modulus in [17u32, 19u32] {
+            let p = BigUint::from(modulus);
+            let mut residues = 0u32;
+            for value in 0..modulus {
+                let a = FiniteField::new(BigUint::from(value), p.clone());
+                match a.sqrt() {
+                    Some(root) => {
+                        assert_eq!(root.mul(&root), Ok(a));
+                        residues += 1;
+                    }
+                    None => assert!((0..modulus).all(|y| (y * y) % modulus != value)),
+                }
+            }
+            assert_eq!(residues, (modulus - 1) / 2 + 1);
+        }
+    }
+
+    #[test]
+    fn test_double_and_mul_small() {
+        let p = BigUint::from(17u32);
+        for value in 0..17u32 {
+            let a = FiniteField::new(BigUint::from(value), p.clone());
+            assert_eq!(Ok(a.double()), a.add(&a), "a = {}", value);
+            assert_eq!(Ok(a.mul_small(3)), a.add(&a).unwrap().add(&a), "a = {}", value);
+            assert_eq!(a.mul_small(0).value, BigUint::from(0u32));
+            assert_eq!(a.mul_small(17 * 5 + 2), a.double());
+            assert_eq!(FieldElement::mul_small(&a, 3), Ok(a.mul_small(3)));
+        }
+
+        // an unreduced value still doubles into [0, p)
+        let unreduced = FiniteField { value: BigUint::from(30u32), p: p.clone() };
+        assert_eq!(unreduced.double().value, BigUint::from(9u32));
+    }
+
+    #[test]
+    fn test_sub_assign_and_mul_assign() {
+        let p = BigUint::from(17u32);
+        for a in 0..17u32 {
+            for b in 0..17u32 {
+                let (x, y) = (FiniteField::new(BigUint::from(a), p.clone()), FiniteField::new(BigUint::from(b), p.clone()));
+                let mut difference = x.clone();
+                difference.sub_assign(&y).unwrap();
+                assert_eq!(Ok(difference), x.sub(&y), "{} - {}", a, b);
+                let mut product = x.clone();
+                product.mul_assign(&y).unwrap();
+                assert_eq!(Ok(product), x.mul(&y), "{} * {}", a, b);
+            }
+        }
+
+        // unreduced values are reduced first, as in sub
+        let mut unreduced = FiniteField { value: BigUint::from(20u32), p: p.clone() };
+        unreduced.sub_assign(&FiniteField { value: BigUint::from(35u32), p: p.clone() }).unwrap();
+        assert_eq!(unreduced.value, BigUint::from(2u32));
+        assert!(unreduced.sub_assign(&FiniteField::new(BigUint::from(1u32), BigUint::from(19u32))).is_err());
+    }
+
+    #[test]
+    fn test_sqrt_ratio() {
+        // 17 exercises the Tonelli-Shanks loop (p - 1 = 2^4 * 1), 19 skips it (p - 1 = 2 * 9)
+        for (modulus, z) in [(17u32, 3u32), (19, 2)] {
+            let p = BigUint::from(modulus);
+            let element = |value: u32| FiniteField::new(BigUint::from(value), p.clone());
+            for u in 0..modulus {
+                for v in 1..modulus {
+                    let ratio = element(u).div(&element(v)).unwrap();
+                    let (is_qr, root) = FiniteField::sqrt_ratio(&element(u), &element(v)).unwrap();
+
+                    match ratio.sqrt() {
+                        Some(expected) => {
+                            assert!(is_qr, "u = {}, v = {}", u, v);
+                            assert!(root == expected || root == expected.neg(), "u = {}, v = {}", u, v);
+                        }
+                        None => {
+                            assert!(!is_qr, "u = {}, v = {}", u, v);
+                            assert_eq!(root.mul(&root), element(z).mul(&ratio), "u = {}, v = {}", u, v);
+                        }
+                    }
+                }
+            }
+
+            assert!(FiniteField::sqrt_ratio(&element(1), &element(0)).is_err());
+        }
+        let a = FiniteField::new(BigUint::from(1u32), BigUint::from(17u32));
+        assert!(FiniteField::sqrt_ratio(&a, &FiniteField::new(BigUint::from(1u32), BigUint::from(19u32))).is_err());
+
+        // p = 2 has no shift to make (p - 1 = 1), and 15 is odd but not prime
+        for modulus in [2u32, 15] {
+            let element = |value: u32| FiniteField::new(BigUint::from(value), BigUint::from(modulus));
+            assert_eq!(FiniteField::sqrt_ratio(&element(1), &element(1)), Err("Modulus must be an odd prime"));
+        }
+    }
+
+    #[test]
+    fn test_sqrt_ratio_constants_reused() {
+        // 13 = 5 mod 8 (one loop step, like ed25519), 23 = 3 mod 4 (no loop), 41 = 1 mod 8
+        for modulus in [13u32, 23, 41] {
+            let p = BigUint::from(modulus);
+            let constants = SqrtRatio::new(&p).unwrap();
+            let element = |value: u32| FiniteField::new(BigUint::from(value), p.clone());
+            for u in 0..modulus {
+                for v in 1..modulus {
+                    assert_eq!(constants.sqrt_ratio(&element(u), &element(v)), FiniteField::sqrt_ratio(&element(u), &element(v)));
+                    let (is_qr, root) = constants.sqrt_ratio(&element(u), &element(v)).unwrap();
+                    assert_eq!(is_qr, element(u).div(&element(v)).unwrap().sqrt().is_some(), "u = {}, v = {}", u, v);
+                    if is_qr {
+                        assert_eq!(root.mul(&root).unwrap().mul(&element(v)).unwrap(), element(u), "u = {}, v = {}", u, v);
+                    }
+                }
+            }
+            assert!(constants.sqrt_ratio(&FiniteField::new(BigUint::from(1u32), BigUint::from(7u32)), &element(1)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_field_context() {
+        let f7 = Field::new(BigUint::from(7u32));
+        let a = f7.element(BigUint::from(2u32));
+        let b = f7.element(BigUint::from(6u32));
+
+        assert_eq!(f7.add(&a, &b), f7.element(BigUint::from(1u32)));
+        assert_eq!(f7.sub(&a, &b), f7.element(BigUint::from(3u32)));
+        assert_eq!(f7.mul(&a, &b), f7.element(BigUint::from(5u32)));
+        assert_eq!(Ok(f7.add(&a, &b)), a.add(&b));
+
+        // elements of different fields are still rejected by the checked arithmetic
+        let f11 = Field::new(BigUint::from(11u32));
+        let c = f11.element(BigUint::from(2u32));
+        assert!(a.add(&c).is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let a = FiniteField::new(BigUint::from(300u32), BigUint::from(65537u32));
+
+        assert_eq!(BigUint::from(&a), BigUint::from(300u32));
+        assert_eq!(BigUint::from(a.clone()), BigUint::from(300u32));
+        assert_eq!(Vec::<u8>::from(a), vec![0x01, 0x2C]);
+    }
+
+    #[test]
+    fn test_bytes_be() {
+        let p = BigUint::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F", 16).unwrap();
+        let five = FiniteField::new(BigUint::from(5u32), p.clone());
+
+        let bytes = five.to_bytes_be();
+        let mut expected = vec![0u8; 31];
+        expected.push(5);
+        assert_eq!(bytes, expected);
+        assert_eq!(FiniteField::from_bytes_be(&bytes, p.clone()), five);
+
+        // 17 fits in one byte
+        let a = FiniteField::new(BigUint::from(16u32), BigUint::from(17u32));
+        assert_eq!(a.to_bytes_be(), vec![16]);
+    }
+
+    #[test]
+    fn test_legendre_symbol() {
+        let p = BigUint::from(17u32);
+        // the squares mod 17 are 1, 2, 4, 8, 9, 13, 15, 16
+        for residue in [1u32, 2, 4, 8, 9, 13, 15, 16] {
+            assert_eq!(FiniteField::new(BigUint::from(residue), p.clone()).legendre_symbol(), 1, "a = {}", residue);
+        }
+        for non_residue in [3u32, 5, 6, 7, 10, 11, 12, 14] {
+            assert_eq!(FiniteField::new(BigUint::from(non_residue), p.clone()).legendre_symbol(), -1, "a = {}", non_residue);
+        }
+        assert_eq!(FiniteField::new(BigUint::from(0u32), p.clone()).legendre_symbol(), 0);
+        assert_eq!(FiniteField::new(BigUint::from(17u32), p).legendre_symbol(), 0);
+    }
+
+    // arbitrary values up to 320 bits against arbitrary moduli >= 2 (prime or not): no operation
+    // may panic, and every result must be a reduced element of the same field
+    mod fuzz {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn biguint(max_bytes: usize) -> impl Strategy<Value = BigUint> {
+            proptest::collection::vec(any::<u8>(), 0..max_bytes).prop_map(|bytes| BigUint::from_bytes_be(&bytes))
+        }
+
+        // mostly random, sometimes the secp256k1 prime so its specialised inverse is covered too
+        fn modulus() -> impl Strategy<Value = BigUint> {
+            prop_oneof![
+                4 => biguint(40).prop_map(|p| p + 2u32),
+                1 => Just(crate::secp256k1::field_prime()),
+            ]
+        }
+
+        fn assert_reduced(result: &FiniteField, p: &BigUint) {
+            assert_eq!(&result.p, p);
+            assert!(&result.value < p);
+        }
+
+        proptest! {
+            #[test]
+            fn test_operations_stay_in_field(p in modulus(), a in biguint(40), b in biguint(40), e in biguint(40)) {
+                let x = FiniteField::new(a, p.clone());
+                let y = FiniteField::new(b, p.clone());
+                assert_reduced(&x, &p);
+
+                for result in [x.add(&y), x.sub(&y), y.sub(&x), x.mul(&y), x.div(&y), x.inverse()].into_iter().flatten() {
+                    assert_reduced(&result, &p);
+                }
+                assert_reduced(&x.pow(&e), &p);
+
+                // dividing by zero is an error, never a panic
+                prop_assert_eq!(y.div(&y).is_err(), y.value == BigUint::from(0u32));
+            }
+
+            #[test]
+            fn test_mismatched_moduli_are_errors(p in modulus(), q in modulus(), a in biguint(40)) {
+                prop_assume!(p != q);
+                let x = FiniteField::new(a.clone(), p);
+                let y = FiniteField::new(a, q);
+                prop_assert!(x.add(&y).is_err() && x.sub(&y).is_err() && x.mul(&y).is_err() && x.div(&y).is_err());
+            }
+        }
+    }
+}
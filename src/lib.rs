@@ -0,0 +1,74 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod finite_field;
+pub use crate::finite_field::{Field, FieldElement, FiniteField, SqrtRatio};
+
+pub mod scalar;
+pub use crate::scalar::Scalar;
+
+pub mod elliptic_curve;
+pub use crate::elliptic_curve::{CurveError, EllipticCurve, Point};
+
+#[cfg(any(test, feature = "op-counter"))]
+pub mod op_counter;
+
+#[cfg(test)]
+mod test_util;
+
+pub mod secp256k1;
+pub use crate::secp256k1::Secp256k1;
+
+#[cfg(feature = "std")]
+pub mod ecdsa;
+#[cfg(feature = "std")]
+pub use crate::ecdsa::{EcdsaKeyPair, EcdsaSignature, EcdsaSigner, RecoveryId, ValidatedKeyCache};
+
+#[cfg(feature = "std")]
+pub mod bitcoin_message;
+
+#[cfg(feature = "std")]
+pub mod der;
+
+#[cfg(feature = "std")]
+pub mod ecdh;
+
+#[cfg(feature = "std")]
+pub mod base58;
+
+#[cfg(feature = "std")]
+pub mod bech32;
+#[cfg(feature = "std")]
+pub use crate::bech32::{bech32_to_public_key, public_key_to_bech32};
+
+#[cfg(feature = "std")]
+pub mod wif;
+#[cfg(feature = "std")]
+pub use crate::wif::{private_key_to_wif, wif_to_private_key};
+
+#[cfg(feature = "std")]
+pub mod address;
+#[cfg(feature = "std")]
+pub use crate::address::public_key_to_address;
+
+pub mod tagged_hash;
+pub use crate::tagged_hash::{tagged_hash, TaggedHash};
+
+#[cfg(feature = "std")]
+pub mod schnorr;
+
+#[cfg(feature = "std")]
+pub mod ed25519;
+
+#[cfg(feature = "std")]
+pub mod stealth;
+
+#[cfg(feature = "std")]
+pub mod util;
+
+#[cfg(feature = "std")]
+pub mod shamir;
+
+#[cfg(feature = "std")]
+pub mod pedersen;
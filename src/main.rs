@@ -1,29 +1,85 @@
-mod finite_field;
-pub use crate::finite_field::FiniteField;
+use num_bigint::BigUint;
+use num_traits::Zero;
+use ecdsa::util::scalar_from_hex;
+use ecdsa::{EcdsaSignature, Secp256k1};
+use std::io::BufRead;
+use std::process::ExitCode;
 
-mod elliptic_curve;
-pub use crate::elliptic_curve::EllipticCurve;
+const USAGE: &str = "Usage: ecdsa [--key <private key>] [--compressed] [--trace <message>]
 
+Prints the SEC1-encoded secp256k1 public key for a private key given in hex (0x prefix)
+or decimal. Without --key the private key is read from the first line of stdin.
+With --trace the key signs the message instead, printing every intermediate value of the
+signing computation in the layout of OpenSSL's -text output.";
 
-mod secp256k1;
-use num_bigint::BigUint;
-use crate::secp256k1::Secp256k1;
-use crate::elliptic_curve::Point;
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
 
-mod ecdsa;
-pub use crate::ecdsa::{EcdsaKeyPair, EcdsaSignature};
+    match run(&args) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
 
-fn main() {
-    let secp256k1 = Secp256k1::new();
-    let private_key = BigUint::from(123456789u64);
-
-    match Secp256k1::generate_public_key(&secp256k1, private_key) {
-        Ok(public_key) => {
-            match public_key {
-                Point::Coor(x, y) => println!("Public key:\n\nx: {:?}\ny: {:?}", x, y),
-                Point::Identity => println!("Public key is at the identity point."),
-            }
+// the public key as hex (or the signing trace), or a message for a bad argument or key
+fn run(args: &[String]) -> Result<String, String> {
+    let mut key = None;
+    let mut compressed = false;
+    let mut trace = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--key" => key = Some(args.next().ok_or("--key needs a value")?.clone()),
+            "--compressed" => compressed = true,
+            "--trace" => trace = Some(args.next().ok_or("--trace needs a message")?.clone()),
+            other => return Err(format!("Unknown argument '{}'\n\n{}", other, USAGE)),
+        }
+    }
+
+    let key = match key {
+        Some(key) => key,
+        None => {
+            let mut line = String::new();
+            std::io::stdin().lock().read_line(&mut line).map_err(|e| e.to_string())?;
+            line
         }
-        Err(e) => println!("Error: {}", e),
+    };
+
+    let secp256k1 = Secp256k1::new();
+    let private_key = parse_private_key(key.trim())?;
+    if private_key.is_zero() || private_key >= secp256k1.elliptic_curve.n {
+        return Err("Private key must be in the range [1, n-1]".to_string());
+    }
+
+    if let Some(message) = trace {
+        let mut out = Vec::new();
+        EcdsaSignature::sign_verbose(&secp256k1.elliptic_curve, message.as_bytes(), &private_key, &mut out)?;
+        let out = String::from_utf8(out).map_err(|e| e.to_string())?;
+        return Ok(out.trim_end().to_string());
+    }
+
+    let public_key = secp256k1.generate_public_key(private_key)?;
+    let encoded = secp256k1.elliptic_curve.serialize_point(&public_key, compressed)?;
+    Ok(encoded.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+// 0x-prefixed hex, otherwise decimal
+fn parse_private_key(s: &str) -> Result<BigUint, String> {
+    if s.starts_with("0x") || s.starts_with("0X") {
+        return scalar_from_hex(s);
+    }
+    if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid private key '{}', expected 0x-prefixed hex or decimal", s));
     }
+    BigUint::parse_bytes(s.as_bytes(), 10).ok_or_else(|| "Invalid decimal private key".to_string())
 }
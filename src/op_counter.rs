@@ -0,0 +1,41 @@
+use std::cell::Cell;
+
+// Point operation counts for the current thread, to check how many additions and doublings an
+// algorithm performs (e.g. that the Montgomery ladder does the same work for every scalar)
+// compiled in for tests and with the "op-counter" feature, so normal builds pay nothing
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OpCounts {
+    pub additions: u64,
+    pub doublings: u64,
+}
+
+thread_local! {
+    static COUNTS: Cell<OpCounts> = Cell::new(OpCounts::default());
+}
+
+// counts since the last reset on this thread
+pub fn op_counts() -> OpCounts {
+    COUNTS.with(Cell::get)
+}
+
+pub fn reset_op_counts() {
+    COUNTS.with(|counts| counts.set(OpCounts::default()));
+}
+
+// every call to EllipticCurve::add, including ones with the identity or that delegate to double
+pub(crate) fn record_addition() {
+    COUNTS.with(|counts| {
+        let mut current = counts.get();
+        current.additions += 1;
+        counts.set(current);
+    });
+}
+
+// every call to EllipticCurve::double
+pub(crate) fn record_doubling() {
+    COUNTS.with(|counts| {
+        let mut current = counts.get();
+        current.doublings += 1;
+        counts.set(current);
+    });
+}
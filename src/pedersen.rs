@@ -0,0 +1,92 @@
+use num_bigint::BigUint;
+use crate::elliptic_curve::{EllipticCurve, Point};
+
+// domain string hashed to the second generator H; nobody knows log_G(H), since H comes out of
+// try-and-increment hashing rather than a scalar multiple of G
+const H_DOMAIN: &[u8] = b"ecdsa/pedersen/H";
+
+// commitments on one curve, with the second generator H hashed once up front
+pub struct Pedersen<'a> {
+    curve: &'a EllipticCurve,
+    h: Point,
+}
+
+// the second generator H, the same for every commitment on this curve
+// hash_to_point does not clear the cofactor, so on a curve with cofactor > 1 H may have a
+// small-order component; only use this on prime-order curves such as secp256k1 or P-256
+pub fn generator_h(curve: &EllipticCurve) -> Result<Point, String> {
+    curve.hash_to_point(H_DOMAIN).map_err(|e| e.to_string())
+}
+
+impl<'a> Pedersen<'a> {
+    pub fn new(curve: &'a EllipticCurve) -> Result<Self, String> {
+        Ok(Pedersen { curve, h: generator_h(curve)? })
+    }
+
+    pub fn h(&self) -> &Point {
+        &self.h
+    }
+
+    // Pedersen commitment value*G + blinding*H: hiding while the blinding factor is secret and
+    // random, binding as long as log_G(H) is unknown
+    pub fn commit(&self, value: &BigUint, blinding: &BigUint) -> Result<Point, String> {
+        self.curve.scalar_mul_double(value, &self.curve.g, blinding, &self.h).map_err(|e| e.to_string())
+    }
+
+    // commitments are additively homomorphic, so the inputs sum to the outputs exactly when the
+    // committed values and the blinding factors both balance (mod n)
+    pub fn verify_sum(&self, inputs: &[Point], outputs: &[Point]) -> Result<bool, String> {
+        Ok(self.sum(inputs)? == self.sum(outputs)?)
+    }
+
+    fn sum(&self, points: &[Point]) -> Result<Point, String> {
+        let mut total = Point::Identity;
+        for point in points {
+            self.curve.add_in_place(&mut total, point)?;
+        }
+        Ok(total)
+    }
+}
+
+// Test cases for Pedersen commitments
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+
+    #[test]
+    fn test_commitments_add_up() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let pedersen = Pedersen::new(&curve).unwrap();
+        let (a, r_a) = (BigUint::from(25u32), BigUint::from(123456789u64));
+        let (b, r_b) = (BigUint::from(17u32), &curve.n - 1000u32);
+
+        let commit_a = pedersen.commit(&a, &r_a).unwrap();
+        let commit_b = pedersen.commit(&b, &r_b).unwrap();
+        let combined = pedersen.commit(&(&a + &b), &((&r_a + &r_b) % &curve.n)).unwrap();
+
+        assert_eq!(curve.add(&commit_a, &commit_b).as_ref(), Ok(&combined));
+        assert_eq!(pedersen.verify_sum(&[commit_a.clone(), commit_b.clone()], &[combined]), Ok(true));
+
+        // a different value or blinding factor on one side breaks the balance
+        let wrong_value = pedersen.commit(&(&a + &b + 1u32), &((&r_a + &r_b) % &curve.n)).unwrap();
+        assert_eq!(pedersen.verify_sum(&[commit_a.clone(), commit_b.clone()], &[wrong_value]), Ok(false));
+        let wrong_blinding = pedersen.commit(&(&a + &b), &(&r_a + 1u32)).unwrap();
+        assert_eq!(pedersen.verify_sum(&[commit_a, commit_b], &[wrong_blinding]), Ok(false));
+    }
+
+    #[test]
+    fn test_generator_h() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let pedersen = Pedersen::new(&curve).unwrap();
+        let h = pedersen.h();
+        assert_eq!(curve.validate_public_key(h), Ok(()));
+        assert_ne!(h, &curve.g);
+        assert_eq!(generator_h(&curve).as_ref(), Ok(h));
+
+        // the same value commits to different points under different blinding factors
+        let value = BigUint::from(5u32);
+        assert_ne!(pedersen.commit(&value, &BigUint::from(1u32)), pedersen.commit(&value, &BigUint::from(2u32)));
+        assert_eq!(pedersen.commit(&value, &BigUint::from(0u32)), curve.scalar_mul(&curve.g, &value).map_err(|e| e.to_string()));
+    }
+}
@@ -0,0 +1,127 @@
+use num_bigint::BigUint;
+
+// An integer mod the group order n, for private keys, nonces and the ECDSA r and s
+// a distinct type from FiniteField so values mod n and coordinates mod p cannot be mixed up
+// used for the arithmetic inside ecdsa, the public ecdsa types still hold BigUint (see there)
+#[derive(PartialEq, Clone, Debug)]
+pub struct Scalar {
+    pub value: BigUint,
+    pub n: BigUint,
+}
+
+impl Scalar {
+    // value is reduced mod n
+    pub fn new(value: BigUint, n: BigUint) -> Self {
+        Self {value: value % &n, n}
+    }
+
+    // hash to scalar: bits2int (the leftmost bitlen(n) bits of the digest), then reduce mod n,
+    // as signing, verification and RFC 6979 nonce generation all need
+    pub fn from_hash(hash: &[u8], n: &BigUint) -> Self {
+        Scalar::new(bits2int(hash, n), n.clone())
+    }
+
+    pub fn get_value(&self) -> &BigUint {
+        &self.value
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == BigUint::from(0u32)
+    }
+
+    // (a + b) mod n
+    pub fn add(&self, other: &Scalar) -> Result<Scalar, &'static str> {
+        if self.n != other.n {
+            return Err("Operands must be scalars mod the same n");
+        }
+        Ok(Scalar::new(&self.value + &other.value, self.n.clone()))
+    }
+
+    // (a * b) mod n
+    pub fn mul(&self, other: &Scalar) -> Result<Scalar, &'static str> {
+        if self.n != other.n {
+            return Err("Operands must be scalars mod the same n");
+        }
+        Ok(Scalar::new(&self.value * &other.value, self.n.clone()))
+    }
+
+    // -a mod n
+    pub fn neg(&self) -> Scalar {
+        Scalar::new(&self.n - &self.value, self.n.clone())
+    }
+
+    // a^-1 = a^(n-2) mod n, n being the prime order of the group
+    pub fn inverse(&self) -> Result<Scalar, &'static str> {
+        if self.is_zero() {
+            return Err("Cannot invert zero");
+        }
+        let exponent = &self.n - BigUint::from(2u32);
+        Ok(Scalar::new(self.value.modpow(&exponent, &self.n), self.n.clone()))
+    }
+}
+
+// leftmost bitlen(n) bits of the digest as an integer (FIPS 186-4, RFC 6979 bits2int)
+// the bit length comes from the digest buffer itself, so leading zero bytes still count
+pub fn bits2int(digest: &[u8], n: &BigUint) -> BigUint {
+    let value = BigUint::from_bytes_be(digest);
+    let digest_bits = digest.len() as u64 * 8;
+    if digest_bits > n.bits() {
+        value >> (digest_bits - n.bits())
+    } else {
+        value
+    }
+}
+
+impl From<Scalar> for BigUint {
+    fn from(scalar: Scalar) -> Self {
+        scalar.value
+    }
+}
+
+// Test cases for Scalar
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+    use crate::test_util::hex_bytes;
+
+    #[test]
+    fn test_wraps_at_n_not_p() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let n_minus_one = Scalar::new(&curve.n - 1u32, curve.n.clone());
+        let two = Scalar::new(BigUint::from(2u32), curve.n.clone());
+
+        // n - 1 + 2 = 1 mod n, while mod p it would still be n + 1
+        assert_eq!(n_minus_one.add(&two).unwrap().value, BigUint::from(1u32));
+        assert!(&curve.n + 1u32 < curve.p);
+
+        // (n - 1) * 2 = n - 2 mod n
+        assert_eq!(n_minus_one.mul(&two).unwrap().value, &curve.n - 2u32);
+        assert_eq!(Scalar::new(curve.n.clone(), curve.n.clone()).value, BigUint::from(0u32));
+        assert_eq!(two.neg().value, &curve.n - 2u32);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let n = BigUint::from(19u32);
+        for value in 1u32..19 {
+            let a = Scalar::new(BigUint::from(value), n.clone());
+            assert_eq!(a.mul(&a.inverse().unwrap()).unwrap().value, BigUint::from(1u32), "a = {}", value);
+        }
+        assert!(Scalar::new(BigUint::from(19u32), n.clone()).inverse().is_err());
+        assert!(Scalar::new(BigUint::from(3u32), n).add(&Scalar::new(BigUint::from(3u32), BigUint::from(17u32))).is_err());
+    }
+
+    #[test]
+    fn test_from_hash_rfc6979_vector() {
+        // RFC 6979 A.1.2: q has 163 bits, h1 = SHA-256("sample")
+        let q = BigUint::parse_bytes(b"4000000000000000000020108A2E0CC0D99F8A5EF", 16).unwrap();
+        let h1 = hex_bytes("AF2BDBE1AA9B6EC1E2ADE1D694F41FC71A831D0268E9891562113D8A62ADD1BF");
+
+        // bits2int(h1) keeps the leftmost 163 bits, bits2octets(h1) is that mod q
+        assert_eq!(bits2int(&h1, &q), BigUint::parse_bytes(b"5795EDF0D54DB760F156F0EB4A7A0FE38D418E813", 16).unwrap());
+        let scalar = Scalar::from_hash(&h1, &q);
+        assert_eq!(scalar.value, BigUint::parse_bytes(b"01795EDF0D54DB760F156D0DAC04C0322B3A204224", 16).unwrap());
+        assert_eq!(scalar.n, q);
+    }
+}
@@ -0,0 +1,201 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::sync::OnceLock;
+use crate::elliptic_curve::{EllipticCurve, Point};
+use crate::finite_field::FiniteField;
+use crate::tagged_hash::TaggedHash;
+
+// BIP-340 Schnorr signature with fresh auxiliary randomness
+// returns bytes(R.x) || bytes(s)
+pub fn sign(curve: &EllipticCurve, message: &[u8], private_key: &BigUint) -> Result<[u8; 64], String> {
+    let mut aux_rand = [0u8; 32];
+    OsRng.fill_bytes(&mut aux_rand);
+    sign_with_aux(curve, message, private_key, &aux_rand)
+}
+
+// BIP-340 signing with caller-supplied auxiliary randomness (deterministic, used by the test vectors)
+pub fn sign_with_aux(curve: &EllipticCurve, message: &[u8], private_key: &BigUint, aux_rand: &[u8; 32]) -> Result<[u8; 64], String> {
+    if private_key.is_zero() || private_key >= &curve.n {
+        return Err("Private key must be in the range [1, n-1]".to_string());
+    }
+
+    // x-only public key: negate d so that P = d*G has an even y
+    let (p_x, p_y) = coordinates(&curve.mul_generator(private_key)?)?;
+    let d = if p_y.is_even() { private_key.clone() } else { &curve.n - private_key };
+
+    let aux_hash = tags().aux.hash(aux_rand);
+    let mut t = to_32_bytes(&d)?;
+    for (t_byte, aux_byte) in t.iter_mut().zip(aux_hash.iter()) {
+        *t_byte ^= aux_byte;
+    }
+
+    let nonce_input = [&t[..], &to_32_bytes(p_x.get_value())?, message].concat();
+    let k = BigUint::from_bytes_be(&tags().nonce.hash(&nonce_input)) % &curve.n;
+    if k.is_zero() {
+        return Err("Derived nonce is zero".to_string());
+    }
+
+    // even-y nonce point
+    let (r_x, r_y) = coordinates(&curve.mul_generator(&k)?)?;
+    let k = if r_y.is_even() { k } else { &curve.n - k };
+
+    let e = challenge(curve, r_x.get_value(), p_x.get_value(), message)?;
+
+    // s = k + e*d mod n
+    let k_field = FiniteField::new(k, curve.n.clone());
+    let d_field = FiniteField::new(d, curve.n.clone());
+    let s = k_field.add(&e.mul(&d_field)?)?;
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&to_32_bytes(r_x.get_value())?);
+    signature[32..].copy_from_slice(&to_32_bytes(s.get_value())?);
+    Ok(signature)
+}
+
+// BIP-340 verification: s*G = R + e*P with P the even-y point for pubkey_x
+pub fn verify(curve: &EllipticCurve, message: &[u8], pubkey_x: &BigUint, signature: &[u8; 64]) -> Result<bool, String> {
+    let public_key = match lift_x_even(curve, pubkey_x)? {
+        Some(point) => point,
+        None => return Ok(false),
+    };
+
+    let r = BigUint::from_bytes_be(&signature[..32]);
+    let s = BigUint::from_bytes_be(&signature[32..]);
+    if r >= curve.p || s >= curve.n {
+        return Ok(false);
+    }
+
+    let e = challenge(curve, &r, pubkey_x, message)?;
+
+    // R = s*G - e*P = s*G + (n - e)*P
+    let minus_e = (&curve.n - e.get_value()) % &curve.n;
+    let r_point = curve.multi_scalar_mul(&[(s, curve.g.clone()), (minus_e, public_key)])?;
+
+    match r_point {
+        Point::Coor(x, y) => Ok(y.is_even() && x.get_value() == &r),
+        Point::Identity => Ok(false),
+    }
+}
+
+// the BIP-340 tags, each prefix hashed once per process
+struct Bip340Tags {
+    aux: TaggedHash,
+    nonce: TaggedHash,
+    challenge: TaggedHash,
+}
+
+fn tags() -> &'static Bip340Tags {
+    static TAGS: OnceLock<Bip340Tags> = OnceLock::new();
+    TAGS.get_or_init(|| Bip340Tags {
+        aux: TaggedHash::new("BIP0340/aux"),
+        nonce: TaggedHash::new("BIP0340/nonce"),
+        challenge: TaggedHash::new("BIP0340/challenge"),
+    })
+}
+
+// e = int(hash_BIP0340/challenge(bytes(R.x) || bytes(P.x) || m)) mod n
+fn challenge(curve: &EllipticCurve, r_x: &BigUint, p_x: &BigUint, message: &[u8]) -> Result<FiniteField, String> {
+    let input = [&to_32_bytes(r_x)?[..], &to_32_bytes(p_x)?, message].concat();
+    Ok(FiniteField::new(BigUint::from_bytes_be(&tags().challenge.hash(&input)), curve.n.clone()))
+}
+
+// the point with x-coordinate x and even y, None if x is not on the curve
+fn lift_x_even(curve: &EllipticCurve, x: &BigUint) -> Result<Option<Point>, &'static str> {
+    if x >= &curve.p {
+        return Ok(None);
+    }
+    Ok(curve.lift_x(&FiniteField::new(x.clone(), curve.p.clone())).ok().map(|(even, _)| even))
+}
+
+fn coordinates(point: &Point) -> Result<(FiniteField, FiniteField), &'static str> {
+    match point {
+        Point::Coor(x, y) => Ok((x.clone(), y.clone())),
+        Point::Identity => Err("Unexpected point at infinity"),
+    }
+}
+
+// BIP-340 encodes every integer in exactly 32 bytes, which curves over 256 bits cannot meet
+fn to_32_bytes(value: &BigUint) -> Result<[u8; 32], String> {
+    let bytes = value.to_bytes_be();
+    if bytes.len() > 32 {
+        return Err("Value does not fit in 32 bytes".to_string());
+    }
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(out)
+}
+
+// Test cases from the BIP-340 test vectors
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::Num;
+    use crate::secp256k1::Secp256k1;
+    use crate::test_util::hex_array;
+
+    #[test]
+    fn test_bip340_vector_0() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let private_key = BigUint::from(3u32);
+        let pubkey_x = BigUint::from_str_radix("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9", 16).unwrap();
+        let message = [0u8; 32];
+        let expected: [u8; 64] = hex_array("E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0");
+
+        assert_eq!(sign_with_aux(&curve, &message, &private_key, &[0u8; 32]), Ok(expected));
+        assert_eq!(verify(&curve, &message, &pubkey_x, &expected), Ok(true));
+    }
+
+    #[test]
+    fn test_bip340_vector_1() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let private_key = BigUint::from_str_radix("B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF", 16).unwrap();
+        let pubkey_x = BigUint::from_str_radix("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659", 16).unwrap();
+        let message: [u8; 32] = hex_array("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89");
+        let aux_rand: [u8; 32] = hex_array("0000000000000000000000000000000000000000000000000000000000000001");
+        let expected: [u8; 64] = hex_array("6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A");
+
+        assert_eq!(sign_with_aux(&curve, &message, &private_key, &aux_rand), Ok(expected));
+        assert_eq!(verify(&curve, &message, &pubkey_x, &expected), Ok(true));
+
+        // any modification of the signature must fail
+        let mut tampered = expected;
+        tampered[63] ^= 0x01;
+        assert_eq!(verify(&curve, &message, &pubkey_x, &tampered), Ok(false));
+    }
+
+    #[test]
+    fn test_bip340_vector_5_public_key_not_on_curve() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let pubkey_x = BigUint::from_str_radix("EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34", 16).unwrap();
+        let message: [u8; 32] = hex_array("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89");
+        let signature: [u8; 64] = hex_array("6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B");
+
+        assert_eq!(verify(&curve, &message, &pubkey_x, &signature), Ok(false));
+    }
+
+    #[test]
+    fn test_sign_verify_random_aux() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let private_key = BigUint::from(123456789u64);
+        let (pubkey_x, _) = coordinates(&curve.scalar_mul(&curve.g, &private_key).unwrap()).unwrap();
+        let message = b"schnorr message";
+
+        let signature = sign(&curve, message, &private_key).unwrap();
+        assert_eq!(verify(&curve, message, pubkey_x.get_value(), &signature), Ok(true));
+        assert_eq!(verify(&curve, b"other message", pubkey_x.get_value(), &signature), Ok(false));
+    }
+
+    #[test]
+    fn test_wide_values_are_errors() {
+        // P-384 coordinates and scalars need 48 bytes
+        let p384 = EllipticCurve::from_named("P-384").unwrap();
+        let private_key = &p384.n - 1u32;
+        assert!(sign(&p384, b"message", &private_key).is_err());
+
+        let (pubkey_x, _) = coordinates(&p384.g).unwrap();
+        assert!(verify(&p384, b"message", pubkey_x.get_value(), &[0u8; 64]).is_err());
+        assert_eq!(to_32_bytes(&(BigUint::from(1u32) << 256)), Err("Value does not fit in 32 bytes".to_string()));
+    }
+}
@@ -1,7 +1,14 @@
 use num_bigint::BigUint;
-use num_traits::Num;
+use num_traits::Zero;
 use crate::finite_field::FiniteField;
+use alloc::vec::Vec;
 use crate::elliptic_curve::{EllipticCurve, Point};
+#[cfg(feature = "generator-cache")]
+use crate::elliptic_curve::PrecomputedBase;
+#[cfg(feature = "generator-cache")]
+use std::sync::OnceLock;
+#[cfg(all(test, feature = "generator-cache"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub struct Secp256k1 {
     pub elliptic_curve: EllipticCurve,
@@ -9,18 +16,212 @@ pub struct Secp256k1 {
 
 impl Secp256k1 {
     pub fn new() -> Self {
-        let p = BigUint::from_str_radix("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F", 16).unwrap();
-        let a = FiniteField::new(BigUint::from(0u32), p.clone());
-        let b = FiniteField::new(BigUint::from(7u32), p.clone());
-        let g = Point::Coor(FiniteField::new(BigUint::from_str_radix("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap(), p.clone()),FiniteField::new(BigUint::from_str_radix("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap(), p.clone()),
-    );
-
         Secp256k1 {
-            elliptic_curve: EllipticCurve { a, b, p ,g},
+            elliptic_curve: EllipticCurve::from_named("secp256k1").expect("secp256k1 is a named curve"),
         }
     }
 
     pub fn generate_public_key(&self, private_key: BigUint) -> Result<Point, &'static str> {
-        self.elliptic_curve.scalar_mul(&self.elliptic_curve.g, private_key)
+        self.elliptic_curve.mul_generator(&private_key)
+    }
+}
+
+impl Default for Secp256k1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "generator-cache"))]
+static TABLE_BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+// secp256k1 and its generator table, built once per process on first use
+#[cfg(feature = "generator-cache")]
+fn generator_table() -> &'static (EllipticCurve, PrecomputedBase) {
+    static TABLE: OnceLock<(EllipticCurve, PrecomputedBase)> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        #[cfg(test)]
+        TABLE_BUILDS.fetch_add(1, Ordering::SeqCst);
+
+        let curve = Secp256k1::new().elliptic_curve;
+        let table = curve.precompute_base(&curve.g).expect("Generator table precomputation failed");
+        (curve, table)
+    })
+}
+
+// k*G, using the cached generator table when the curve is secp256k1
+pub fn mul_generator(curve: &EllipticCurve, k: &BigUint) -> Result<Point, &'static str> {
+    #[cfg(feature = "generator-cache")]
+    {
+        let (secp256k1, table) = generator_table();
+        if curve == secp256k1 {
+            return curve.mul_precomputed(table, k);
+        }
+    }
+    curve.scalar_mul(&curve.g, k)
+}
+
+// p = 2^256 - c for the secp256k1 field prime
+const FIELD_PRIME_C: u64 = 0x1_0000_03D1;
+const FIELD_PRIME_LIMBS: [u64; 4] = [0xFFFF_FFFE_FFFF_FC2F, u64::MAX, u64::MAX, u64::MAX];
+
+// field elements as four little-endian 64-bit limbs, so the chain below runs without allocating
+type Limbs = [u64; 4];
+
+// x + c, with the carry out of 2^256
+fn add_c(x: &Limbs) -> (Limbs, bool) {
+    let mut out = [0u64; 4];
+    let mut carry = FIELD_PRIME_C as u128;
+    for i in 0..4 {
+        let v = x[i] as u128 + carry;
+        out[i] = v as u64;
+        carry = v >> 64;
+    }
+    (out, carry != 0)
+}
+
+// a * b mod p, folding the high 256 bits of the product back in as hi * c since 2^256 = c mod p
+fn mul_limbs(a: &Limbs, b: &Limbs) -> Limbs {
+    let mut t = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let v = t[i + j] as u128 + (a[i] as u128) * (b[j] as u128) + carry;
+            t[i + j] = v as u64;
+            carry = v >> 64;
+        }
+        t[i + 4] = carry as u64;
+    }
+
+    // lo + hi * c, leaving a fifth limb below 2^34
+    let mut r = [0u64; 4];
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let v = t[i] as u128 + (t[i + 4] as u128) * (FIELD_PRIME_C as u128) + carry;
+        r[i] = v as u64;
+        carry = v >> 64;
     }
-}
\ No newline at end of file
+
+    // fold the fifth limb the same way
+    let mut carry = carry * FIELD_PRIME_C as u128;
+    for limb in r.iter_mut() {
+        let v = *limb as u128 + carry;
+        *limb = v as u64;
+        carry = v >> 64;
+    }
+    if carry != 0 {
+        // wrapped past 2^256, so r is small and adding c cannot carry again
+        r = add_c(&r).0;
+    }
+
+    // r >= p exactly when r + c overflows 2^256, and then r - p = r + c - 2^256
+    match add_c(&r) {
+        (reduced, true) => reduced,
+        _ => r,
+    }
+}
+
+// a^(2^k) * b mod p
+fn square_times_mul(a: &Limbs, k: usize, b: &Limbs) -> Limbs {
+    let mut x = *a;
+    for _ in 0..k {
+        x = mul_limbs(&x, &x);
+    }
+    mul_limbs(&x, b)
+}
+
+// a^-1 = a^(p-2) for the secp256k1 field prime, using the fixed addition chain of libsecp256k1
+// (255 squarings and 15 multiplications, with reductions exploiting p = 2^256 - 2^32 - 977)
+// rather than a generic modpow
+pub fn secp256k1_field_inverse(a: &FiniteField) -> Result<FiniteField, &'static str> {
+    if !is_field_prime(&a.p) {
+        return Err("Operand must be an element of the secp256k1 field");
+    }
+    if a.value.is_zero() {
+        return Err("Cannot invert zero");
+    }
+
+    let mut x1 = [0u64; 4];
+    for (limb, digit) in x1.iter_mut().zip(a.value.iter_u64_digits()) {
+        *limb = digit;
+    }
+
+    // xk = a^(2^k - 1)
+    let x2 = square_times_mul(&x1, 1, &x1);
+    let x3 = square_times_mul(&x2, 1, &x1);
+    let x6 = square_times_mul(&x3, 3, &x3);
+    let x9 = square_times_mul(&x6, 3, &x3);
+    let x11 = square_times_mul(&x9, 2, &x2);
+    let x22 = square_times_mul(&x11, 11, &x11);
+    let x44 = square_times_mul(&x22, 22, &x22);
+    let x88 = square_times_mul(&x44, 44, &x44);
+    let x176 = square_times_mul(&x88, 88, &x88);
+    let x220 = square_times_mul(&x176, 44, &x44);
+    let x223 = square_times_mul(&x220, 3, &x3);
+
+    // the low 33 bits of p - 2 are 0, 22 ones, then 0000101101
+    let t = square_times_mul(&x223, 23, &x22);
+    let t = square_times_mul(&t, 5, &x1);
+    let t = square_times_mul(&t, 3, &x2);
+    let t = square_times_mul(&t, 2, &x1);
+
+    let digits: Vec<u32> = t.iter().flat_map(|limb| [*limb as u32, (*limb >> 32) as u32]).collect();
+    Ok(FiniteField::new_reduced(BigUint::new(digits), a.p.clone()))
+}
+
+// whether p is the secp256k1 field prime, by comparing limbs without allocating
+pub fn is_field_prime(p: &BigUint) -> bool {
+    p.iter_u64_digits().eq(FIELD_PRIME_LIMBS)
+}
+
+// the secp256k1 field prime, built from its limbs so it is available without std
+pub fn field_prime() -> BigUint {
+    BigUint::new(FIELD_PRIME_LIMBS.iter().flat_map(|limb| [*limb as u32, (*limb >> 32) as u32]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "generator-cache")]
+    fn test_generator_table_cached() {
+        let secp256k1 = Secp256k1::new();
+        let curve = &secp256k1.elliptic_curve;
+
+        for k in [1u64, 2, 7, 123456789, u64::MAX] {
+            let k = BigUint::from(k);
+            assert_eq!(mul_generator(curve, &k), curve.scalar_mul(&curve.g, &k));
+        }
+        assert_eq!(secp256k1.generate_public_key(BigUint::from(3u32)), curve.scalar_mul(&curve.g, &BigUint::from(3u32)));
+
+        // the table is shared, not rebuilt per call or per Secp256k1 instance
+        assert!(std::ptr::eq(generator_table(), generator_table()));
+        assert_eq!(TABLE_BUILDS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_field_inverse_matches_modpow() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let p = field_prime();
+        assert_eq!(p, BigUint::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F", 16).unwrap());
+        let exponent = &p - BigUint::from(2u32);
+        let mut rng = ChaCha20Rng::seed_from_u64(11);
+
+        // edge values around p and 2^256 plus random elements
+        let mut values = vec![BigUint::from(1u32), BigUint::from(2u32), &p - 1u32, &p - 2u32, (BigUint::from(1u32) << 255u32) + 1u32];
+        values.extend((0..50).map(|_| num_bigint::RandBigInt::gen_biguint_below(&mut rng, &p)));
+
+        for value in values.into_iter().filter(|v| !v.is_zero()) {
+            let a = FiniteField::new(value.clone(), p.clone());
+            let inverse = secp256k1_field_inverse(&a).unwrap();
+            assert_eq!(inverse.value, value.modpow(&exponent, &p), "a = {:x}", value);
+            assert_eq!(a.mul(&inverse).unwrap().value, BigUint::from(1u32));
+        }
+
+        assert!(secp256k1_field_inverse(&FiniteField::new(BigUint::from(0u32), p.clone())).is_err());
+        assert!(secp256k1_field_inverse(&FiniteField::new(BigUint::from(3u32), BigUint::from(17u32))).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
+use crate::finite_field::FiniteField;
+
+// Shamir t-of-n secret sharing over the prime field of order modulus (e.g. the group order n
+// to split a private key): f(x) = secret + c1*x + ... + c(t-1)*x^(t-1) with random c1..c(t-1),
+// share i is (i, f(i)) for i in 1..=n, and any t shares determine f(0) = secret
+pub fn split<R: RngCore + CryptoRng>(secret: &BigUint, t: usize, n: usize, modulus: &BigUint, rng: &mut R) -> Result<Vec<(BigUint, BigUint)>, String> {
+    if t == 0 || t > n {
+        return Err("Threshold must be in the range [1, n]".to_string());
+    }
+    if &BigUint::from(n) >= modulus {
+        return Err("Share count must be less than the modulus".to_string());
+    }
+    if secret >= modulus {
+        return Err("Secret must be less than the modulus".to_string());
+    }
+
+    let mut coefficients = vec![FiniteField::new_reduced(secret.clone(), modulus.clone())];
+    coefficients.extend((1..t).map(|_| FiniteField::new_reduced(rng.gen_biguint_below(modulus), modulus.clone())));
+
+    (1..=n).map(|i| {
+        let x = FiniteField::new(BigUint::from(i), modulus.clone());
+        // Horner's rule from the highest coefficient down
+        let mut y = FiniteField::new_reduced(BigUint::from(0u32), modulus.clone());
+        for coefficient in coefficients.iter().rev() {
+            y = y.mul(&x)?.add(coefficient)?;
+        }
+        Ok((x.value, y.value))
+    }).collect::<Result<Vec<_>, &'static str>>().map_err(|e| e.to_string())
+}
+
+// Lagrange interpolation at 0: secret = sum y_i * prod_{j != i} x_j / (x_j - x_i)
+// fewer than t shares give an unrelated value rather than an error, the threshold is not encoded
+pub fn reconstruct(shares: &[(BigUint, BigUint)], modulus: &BigUint) -> Result<BigUint, String> {
+    if shares.is_empty() {
+        return Err("At least one share is required".to_string());
+    }
+    let xs: Vec<FiniteField> = shares.iter().map(|(x, _)| FiniteField::new(x.clone(), modulus.clone())).collect();
+    for (i, x) in xs.iter().enumerate() {
+        if x.value.is_zero() || xs[..i].contains(x) {
+            return Err("Share x-coordinates must be distinct and nonzero".to_string());
+        }
+    }
+
+    let mut secret = FiniteField::new_reduced(BigUint::from(0u32), modulus.clone());
+    for (i, (x_i, (_, y_i))) in xs.iter().zip(shares).enumerate() {
+        let mut numerator = FiniteField::new(y_i.clone(), modulus.clone());
+        let mut denominator = FiniteField::new_reduced(BigUint::from(1u32), modulus.clone());
+        for (j, x_j) in xs.iter().enumerate() {
+            if i != j {
+                numerator = numerator.mul(x_j)?;
+                denominator = denominator.mul(&x_j.sub(x_i)?)?;
+            }
+        }
+        secret = secret.add(&numerator.div(&denominator)?)?;
+    }
+
+    Ok(secret.value)
+}
+
+// Test cases for Shamir secret sharing
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_split_3_of_5() {
+        let n = Secp256k1::new().elliptic_curve.n;
+        let secret = OsRng.gen_biguint_below(&n);
+        let shares = split(&secret, 3, 5, &n, &mut OsRng).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for subset in [[0, 1, 2], [0, 2, 4], [1, 3, 4], [4, 2, 3]] {
+            let chosen: Vec<(BigUint, BigUint)> = subset.iter().map(|i| shares[*i].clone()).collect();
+            assert_eq!(reconstruct(&chosen, &n), Ok(secret.clone()), "shares {:?}", subset);
+        }
+
+        // all five shares lie on the same polynomial, two do not determine it
+        assert_eq!(reconstruct(&shares, &n), Ok(secret.clone()));
+        assert_ne!(reconstruct(&shares[..2], &n), Ok(secret));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let p = BigUint::from(17u32);
+        let secret = BigUint::from(5u32);
+        assert!(split(&secret, 0, 5, &p, &mut OsRng).is_err());
+        assert!(split(&secret, 6, 5, &p, &mut OsRng).is_err());
+        assert!(split(&secret, 3, 17, &p, &mut OsRng).is_err());
+        assert!(split(&p, 3, 5, &p, &mut OsRng).is_err());
+
+        let shares = split(&secret, 2, 3, &p, &mut OsRng).unwrap();
+        let duplicated = [shares[0].clone(), shares[0].clone()];
+        assert_eq!(reconstruct(&duplicated, &p), Err("Share x-coordinates must be distinct and nonzero".to_string()));
+        assert!(reconstruct(&[], &p).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use num_bigint::{BigUint, RandBigInt};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use crate::elliptic_curve::{EllipticCurve, Point};
+
+// Dual-key stealth address: the recipient publishes a scan key A = a*G and a spend key B = b*G.
+// The sender picks an ephemeral r, publishes R = r*G and pays to the one-time key
+// P = B + H(r*A)*G. Only the holder of a can find the payment (a*R = r*A), and only the
+// holder of b can spend it with the one-time private key b + H(a*R) mod n.
+#[derive(PartialEq, Clone, Debug)]
+pub struct StealthAddress {
+    pub one_time_key: Point,
+    pub ephemeral_key: Point,
+}
+
+// sender side, with a fresh ephemeral key
+pub fn create_stealth_address(curve: &EllipticCurve, scan_public: &Point, spend_public: &Point) -> Result<StealthAddress, String> {
+    let ephemeral_private = OsRng.gen_biguint_range(&BigUint::from(1u32), &curve.n);
+    create_stealth_address_with_ephemeral(curve, scan_public, spend_public, &ephemeral_private)
+}
+
+// sender side, with a caller-supplied ephemeral private key r
+pub fn create_stealth_address_with_ephemeral(curve: &EllipticCurve, scan_public: &Point, spend_public: &Point, ephemeral_private: &BigUint) -> Result<StealthAddress, String> {
+    let ephemeral_key = curve.mul_generator(ephemeral_private)?;
+    let shared = curve.scalar_mul(scan_public, ephemeral_private)?;
+    let tweak = shared_secret_scalar(curve, &shared)?;
+
+    let tweak_point = curve.mul_generator(&tweak)?;
+    let one_time_key = curve.add(spend_public, &tweak_point)?;
+
+    Ok(StealthAddress { one_time_key, ephemeral_key })
+}
+
+// recipient side: the one-time private key b + H(a*R) mod n, or None if the address was not made for this recipient
+pub fn recover_stealth_private(curve: &EllipticCurve, scan_private: &BigUint, spend_private: &BigUint, address: &StealthAddress) -> Result<Option<BigUint>, String> {
+    let shared = curve.scalar_mul(&address.ephemeral_key, scan_private)?;
+    let tweak = shared_secret_scalar(curve, &shared)?;
+
+    let private_key = (spend_private + tweak) % &curve.n;
+    if curve.mul_generator(&private_key)? == address.one_time_key {
+        Ok(Some(private_key))
+    } else {
+        Ok(None)
+    }
+}
+
+// H(S) = SHA-256(SEC1 compressed S) mod n
+fn shared_secret_scalar(curve: &EllipticCurve, shared: &Point) -> Result<BigUint, String> {
+    let encoded = curve.serialize_point(shared, true)?;
+    Ok(BigUint::from_bytes_be(&Sha256::digest(encoded)) % &curve.n)
+}
+
+// Test cases for stealth addresses
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+
+    #[test]
+    fn test_recipient_recovers_one_time_private_key() {
+        let curve = Secp256k1::new().elliptic_curve;
+        let scan_private = BigUint::from(1111u32);
+        let spend_private = BigUint::from(2222u32);
+        let scan_public = curve.scalar_mul(&curve.g, &scan_private).unwrap();
+        let spend_public = curve.scalar_mul(&curve.g, &spend_private).unwrap();
+
+        let address = create_stealth_address(&curve, &scan_public, &spend_public).unwrap();
+        assert_ne!(address.one_time_key, spend_public);
+
+        let private_key = recover_stealth_private(&curve, &scan_private, &spend_private, &address).unwrap().unwrap();
+        assert_eq!(curve.scalar_mul(&curve.g, &private_key).unwrap(), address.one_time_key);
+
+        // a different recipient does not detect the payment
+        let other = recover_stealth_private(&curve, &BigUint::from(3333u32), &spend_private, &address).unwrap();
+        assert_eq!(other, None);
+    }
+}
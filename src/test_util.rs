@@ -0,0 +1,14 @@
+use alloc::vec::Vec;
+
+// helpers shared by the test modules
+
+// big-endian hex string (no 0x prefix, even length) into bytes
+pub(crate) fn hex_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+// same, for a fixed-size field such as a 32-byte key or a 64-byte signature
+#[cfg(feature = "std")]
+pub(crate) fn hex_array<const N: usize>(hex: &str) -> [u8; N] {
+    hex_bytes(hex).try_into().unwrap()
+}
@@ -0,0 +1,59 @@
+use num_bigint::BigUint;
+
+// parse a big-endian hex string, with or without a 0x prefix, into an integer
+pub fn scalar_from_hex(s: &str) -> Result<BigUint, String> {
+    let digits = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    if digits.is_empty() {
+        return Err("Empty hex string".to_string());
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err("Hex string has an odd number of digits".to_string());
+    }
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid hex character '{}'", c));
+    }
+
+    BigUint::parse_bytes(digits.as_bytes(), 16).ok_or_else(|| "Invalid hex string".to_string())
+}
+
+pub fn scalar_from_bytes_be(bytes: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(bytes)
+}
+
+// big-endian bytes left-padded with zeros to exactly width bytes
+pub fn scalar_to_bytes_be(s: &BigUint, width: usize) -> Result<Vec<u8>, String> {
+    let bytes = s.to_bytes_be();
+    if bytes.len() > width {
+        return Err(format!("Value does not fit in {} bytes", width));
+    }
+
+    let mut padded = vec![0u8; width - bytes.len()];
+    padded.extend(bytes);
+    Ok(padded)
+}
+
+// Test cases for the conversion helpers
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_from_hex() {
+        let expected = BigUint::from(0xDEADBEEFu32);
+        assert_eq!(scalar_from_hex("deadbeef"), Ok(expected.clone()));
+        assert_eq!(scalar_from_hex("0xDEADBEEF"), Ok(expected));
+
+        assert_eq!(scalar_from_hex("0xabc"), Err("Hex string has an odd number of digits".to_string()));
+        assert_eq!(scalar_from_hex("zz"), Err("Invalid hex character 'z'".to_string()));
+        assert!(scalar_from_hex("0x").is_err());
+    }
+
+    #[test]
+    fn test_fixed_width_bytes() {
+        let s = scalar_from_bytes_be(&[0x01, 0x02]);
+        assert_eq!(s, BigUint::from(0x0102u32));
+        assert_eq!(scalar_to_bytes_be(&s, 4), Ok(vec![0x00, 0x00, 0x01, 0x02]));
+        assert_eq!(scalar_to_bytes_be(&BigUint::from(0u32), 2), Ok(vec![0x00, 0x00]));
+        assert!(scalar_to_bytes_be(&s, 1).is_err());
+    }
+}
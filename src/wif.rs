@@ -0,0 +1,90 @@
+use num_bigint::BigUint;
+use crate::base58;
+
+const MAINNET_VERSION: u8 = 0x80;
+const TESTNET_VERSION: u8 = 0xEF;
+const COMPRESSED_FLAG: u8 = 0x01;
+
+// Wallet Import Format: Base58Check(version || 32-byte key || [0x01 if compressed])
+// the key must fit in 32 bytes
+pub fn private_key_to_wif(key: &BigUint, compressed: bool, testnet: bool) -> String {
+    let key_bytes = key.to_bytes_be();
+
+    let mut payload = vec![if testnet { TESTNET_VERSION } else { MAINNET_VERSION }];
+    payload.resize(1 + 32usize.saturating_sub(key_bytes.len()), 0);
+    payload.extend_from_slice(&key_bytes);
+    if compressed {
+        payload.push(COMPRESSED_FLAG);
+    }
+
+    base58::encode_check(&payload)
+}
+
+// returns the private key and whether it is meant for a compressed public key
+pub fn wif_to_private_key(wif: &str) -> Result<(BigUint, bool), String> {
+    let payload = base58::decode_check(wif)?;
+
+    // the length is checked first, the payload of untrusted input may be empty
+    let compressed = match payload.len() {
+        33 => false,
+        34 if payload[33] == COMPRESSED_FLAG => true,
+        34 => return Err("Invalid WIF compression flag".to_string()),
+        _ => return Err("Invalid WIF length".to_string()),
+    };
+
+    if payload[0] != MAINNET_VERSION && payload[0] != TESTNET_VERSION {
+        return Err("Invalid WIF version byte".to_string());
+    }
+
+    Ok((BigUint::from_bytes_be(&payload[1..33]), compressed))
+}
+
+// Test cases for WIF encoding
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::Num;
+
+    #[test]
+    fn test_wif_known_vector() {
+        let key = BigUint::from_str_radix("0C28FCA386C7A227600B2FE50B7CAE11EC86D3BF1FBE471BE89827E19D72AA1D", 16).unwrap();
+
+        assert_eq!(private_key_to_wif(&key, false, false), "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ");
+        assert_eq!(private_key_to_wif(&key, true, false), "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617");
+        assert_eq!(wif_to_private_key("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"), Ok((key.clone(), false)));
+        assert_eq!(wif_to_private_key("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617"), Ok((key, true)));
+    }
+
+    #[test]
+    fn test_wif_round_trip() {
+        for key in [BigUint::from(1u32), BigUint::from(123456789u64)] {
+            for compressed in [false, true] {
+                for testnet in [false, true] {
+                    let wif = private_key_to_wif(&key, compressed, testnet);
+                    assert_eq!(wif_to_private_key(&wif), Ok((key.clone(), compressed)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wif_rejects_malformed() {
+        // last character changed, checksum no longer matches
+        assert!(wif_to_private_key("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTK").is_err());
+        // '0' is not in the Base58 alphabet
+        assert!(wif_to_private_key("0HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ").is_err());
+        // valid Base58Check, but not a WIF payload
+        assert!(wif_to_private_key(&base58::encode_check(&[0x80, 0x01])).is_err());
+        assert!(wif_to_private_key(&base58::encode_check(&[0x00; 33])).is_err());
+    }
+
+    #[test]
+    fn test_wif_rejects_wrong_length() {
+        // an empty payload must be an error rather than an out-of-bounds read
+        assert_eq!(wif_to_private_key(&base58::encode_check(&[])), Err("Invalid WIF length".to_string()));
+        assert_eq!(wif_to_private_key(&base58::encode_check(&[0x80])), Err("Invalid WIF length".to_string()));
+        assert_eq!(wif_to_private_key(&base58::encode_check(&[0x80; 32])), Err("Invalid WIF length".to_string()));
+        assert_eq!(wif_to_private_key(&base58::encode_check(&[0x80; 35])), Err("Invalid WIF length".to_string()));
+        assert_eq!(wif_to_private_key(&base58::encode_check(&[0x80; 34])), Err("Invalid WIF compression flag".to_string()));
+    }
+}
@@ -0,0 +1,80 @@
+use ecdsa::{EcdsaSignature, EllipticCurve, Point};
+use num_bigint::BigUint;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+// the 123456789 * G public key, compressed and uncompressed
+const COMPRESSED: &str = "0208f4f37e2d8f74e18c1b8fde2374d5f28402fb8ab7fd1cc5b786aa40851a70cb";
+const UNCOMPRESSED: &str = "0408f4f37e2d8f74e18c1b8fde2374d5f28402fb8ab7fd1cc5b786aa40851a70cbc2eca87b8bd2c0be52698e9d5ee19840c4d40ca696e16159134769fa1ae85b2e";
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ecdsa"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start the ecdsa binary");
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap().trim().to_string()
+}
+
+#[test]
+fn test_hex_and_decimal_keys() {
+    assert_eq!(stdout(&run(&["--key", "123456789", "--compressed"], "")), COMPRESSED);
+    assert_eq!(stdout(&run(&["--key", "0x075BCD15", "--compressed"], "")), COMPRESSED);
+    assert_eq!(stdout(&run(&["--key", "0x075bcd15"], "")), UNCOMPRESSED);
+    assert_eq!(stdout(&run(&["--key", "0x1A2B", "--compressed"], "")), "024166c19dbee9ad0883b217aa3b65743687677792d5cb9b311a097fcd2eb033da");
+}
+
+#[test]
+fn test_key_from_stdin() {
+    assert_eq!(stdout(&run(&["--compressed"], "123456789\n")), COMPRESSED);
+}
+
+#[test]
+fn test_invalid_keys_rejected() {
+    // 0, n and a malformed key
+    for key in ["0", "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", "12ab"] {
+        let output = run(&["--key", key], "");
+        assert!(!output.status.success(), "key {}", key);
+        assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "), "key {}", key);
+    }
+    assert!(!run(&["--verbose"], "").status.success());
+}
+
+// the bytes printed under a label, read back as an integer
+fn traced_value(trace: &str, label: &str) -> BigUint {
+    let hex: String = trace.lines()
+                           .skip_while(|l| *l != format!("{}:", label))
+                           .skip(1)
+                           .take_while(|l| l.starts_with("    "))
+                           .flat_map(|l| l.trim().split(':').filter(|byte| !byte.is_empty()))
+                           .collect();
+    BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()
+}
+
+#[test]
+fn test_trace_signs_and_prints_openssl_layout() {
+    let trace = stdout(&run(&["--key", "123456789", "--trace", "Hello, world"], ""));
+    let labels: Vec<&str> = trace.lines().filter(|l| !l.starts_with("    ")).collect();
+    assert_eq!(labels, ["hash:", "truncated hash:", "k:", "R.x:", "R.y:", "r:", "s:", "low-S s (not applied):"]);
+    // the SHA-256 digest wraps after 15 bytes, like OpenSSL's -text output
+    assert!(trace.starts_with("hash:\n    \
+        4a:e7:c3:b6:ac:0b:ef:f6:71:ef:a8:cf:57:38:61:\n    \
+        51:c0:6e:58:ca:53:a7:8d:83:f3:61:07:31:6c:ec:\n    \
+        12:5f\n"), "{}", trace);
+
+    // the printed r and s are a valid signature by the --key key
+    let curve = EllipticCurve::from_named("secp256k1").unwrap();
+    let signature = EcdsaSignature { r: traced_value(&trace, "r"), s: traced_value(&trace, "s") };
+    let public_key: Point = curve.scalar_mul(&curve.g, &BigUint::from(123456789u32)).unwrap();
+    assert_eq!(EcdsaSignature::verify(&curve, b"Hello, world", &public_key, &signature), Ok(true));
+
+    assert!(!run(&["--key", "123456789", "--trace"], "").status.success());
+}
@@ -0,0 +1,19 @@
+// Smoke test for the no_std build, run it as
+//     cargo test --no-default-features --test no_std_smoke
+// alongside the unit tests of the modules that build without std
+//     cargo test --no-default-features --lib
+// the library is then compiled with #![no_std] + alloc, so this also checks that the curve
+// math does not reach for std
+use ecdsa::{EllipticCurve, FiniteField, Point};
+use num_bigint::BigUint;
+
+#[test]
+fn test_curve_and_scalar_mul_without_std() {
+    let curve = EllipticCurve::from_named("secp256k1").unwrap();
+    let twice = curve.scalar_mul(&curve.g, &BigUint::from(2u32)).unwrap();
+
+    let x = BigUint::parse_bytes(b"C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5", 16).unwrap();
+    let y = BigUint::parse_bytes(b"1AE168FEA63DC339A3C58419466CEAEEF7F632653266D0E1236431A950CFE52A", 16).unwrap();
+    assert_eq!(twice, Point::Coor(FiniteField::new(x, curve.p.clone()), FiniteField::new(y, curve.p.clone())));
+    assert_eq!(curve.scalar_mul(&curve.g, &curve.n), Ok(Point::Identity));
+}